@@ -0,0 +1,51 @@
+/* C interface to the Intcode machine of advent_of_code_2019.
+ *
+ * Build the shared library with `cargo build --lib`, it ends up as
+ * target/debug/libadvent_of_code_2019.so (.dylib on macOS).
+ */
+#ifndef INTCODE_H
+#define INTCODE_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define INTCODE_ERROR (-1)
+#define INTCODE_OUTPUT 0
+#define INTCODE_NEED_INPUT 1
+#define INTCODE_HALTED 2
+
+typedef struct IntcodeHandle IntcodeHandle;
+
+/* Creates a machine loaded with len words from program. */
+IntcodeHandle *intcode_new(const int64_t *program, size_t len);
+
+/* Queues an input value. */
+void intcode_push_input(IntcodeHandle *vm, int64_t value);
+
+/* Runs until the next event and returns one of the INTCODE_* codes. */
+int32_t intcode_run_until_event(IntcodeHandle *vm);
+
+/* The value of the most recent INTCODE_OUTPUT event. */
+int64_t intcode_output(const IntcodeHandle *vm);
+
+/* The memory value at address, unset cells read as 0. */
+int64_t intcode_read(const IntcodeHandle *vm, int64_t address);
+
+/* The error message after INTCODE_ERROR, NULL otherwise. Owned by vm. */
+const char *intcode_error(const IntcodeHandle *vm);
+
+/* An independent copy of the machine that must be freed separately. */
+IntcodeHandle *intcode_snapshot(const IntcodeHandle *vm);
+
+/* Releases a machine. NULL is ignored. */
+void intcode_free(IntcodeHandle *vm);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,62 @@
+/* Exercises the C interface. Run with `make -C ffi test`. */
+#include <stdio.h>
+#include <string.h>
+
+#include "intcode.h"
+
+static int failures = 0;
+
+#define CHECK(condition) do { \
+    if (!(condition)) { \
+        fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #condition); \
+        failures++; \
+    } \
+} while (0)
+
+static void test_echo_and_snapshot(void) {
+    const int64_t program[] = {3, 0, 4, 0, 99};
+    IntcodeHandle *vm = intcode_new(program, sizeof(program) / sizeof(program[0]));
+    CHECK(intcode_run_until_event(vm) == INTCODE_NEED_INPUT);
+
+    IntcodeHandle *copy = intcode_snapshot(vm);
+    intcode_push_input(vm, 7);
+    CHECK(intcode_run_until_event(vm) == INTCODE_OUTPUT);
+    CHECK(intcode_output(vm) == 7);
+    CHECK(intcode_run_until_event(vm) == INTCODE_HALTED);
+    CHECK(intcode_read(vm, 0) == 7);
+    intcode_free(vm);
+
+    CHECK(intcode_read(copy, 0) == 3);
+    intcode_push_input(copy, 8);
+    CHECK(intcode_run_until_event(copy) == INTCODE_OUTPUT);
+    CHECK(intcode_output(copy) == 8);
+    intcode_free(copy);
+}
+
+static void test_large_values(void) {
+    const int64_t program[] = {104, 1125899906842624, 99};
+    IntcodeHandle *vm = intcode_new(program, 3);
+    CHECK(intcode_run_until_event(vm) == INTCODE_OUTPUT);
+    CHECK(intcode_output(vm) == 1125899906842624);
+    intcode_free(vm);
+}
+
+static void test_error(void) {
+    const int64_t program[] = {42};
+    IntcodeHandle *vm = intcode_new(program, 1);
+    CHECK(intcode_error(vm) == NULL);
+    CHECK(intcode_run_until_event(vm) == INTCODE_ERROR);
+    CHECK(intcode_error(vm) != NULL && strcmp(intcode_error(vm), "Invalid op code 42 at ip=0") == 0);
+    intcode_free(vm);
+    intcode_free(NULL);
+}
+
+int main(void) {
+    test_echo_and_snapshot();
+    test_large_values();
+    test_error();
+    if (failures == 0) {
+        printf("All checks passed\n");
+    }
+    return failures == 0 ? 0 : 1;
+}
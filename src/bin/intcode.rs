@@ -0,0 +1,217 @@
+//! Runs an Intcode program file outside of any `Solver`.
+
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::process;
+
+use advent_of_code_2019::intcode_computer::memory;
+use advent_of_code_2019::intcode_computer::{apply_patches, Event, IntcodeError, IntcodeVm, Patch};
+
+const USAGE: &str = "Usage: intcode [OPTIONS] PROGRAM [INPUT]...
+
+Inputs given as arguments are used first, after that one input per line is
+read from stdin.
+
+Options:
+    --ascii                 Inputs are text lines sent as characters plus a
+                            newline, outputs below 128 are printed as text
+    --patch ADDRESS=OLD:NEW Set a program value before running, failing if
+                            the program holds anything but OLD
+    --trace                 Print the applied patches and every executed
+                            instruction to stderr
+    --record LOG            Write the consumed inputs and produced outputs
+                            to LOG, to be replayed later
+    --limit N               Stop with an error after N instructions
+    --dump                  Print the final non-zero memory after the program
+                            halts
+    --diff                  Print the cells the run changed, marking those
+                            past the end of the program with +";
+
+#[derive(PartialEq, Eq, Debug, Default)]
+struct Options {
+    program: String,
+    inputs: Vec<String>,
+    patches: Vec<(usize, i128, i128)>,
+    record: Option<String>,
+    ascii: bool,
+    trace: bool,
+    limit: Option<u64>,
+    dump: bool,
+    diff: bool,
+}
+
+fn parse_patch(patch: &str) -> Result<(usize, i128, i128), String> {
+    let invalid = || format!("Invalid patch '{}', expected ADDRESS=OLD:NEW", patch);
+    let (address, values) = patch.split_once('=').ok_or_else(invalid)?;
+    let (old, new) = values.split_once(':').ok_or_else(invalid)?;
+    Ok((
+        address.trim().parse().map_err(|_| invalid())?,
+        old.trim().parse().map_err(|_| invalid())?,
+        new.trim().parse().map_err(|_| invalid())?,
+    ))
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("Missing value for {}", name));
+        match arg.as_str() {
+            "--ascii" => options.ascii = true,
+            "--trace" => options.trace = true,
+            "--dump" => options.dump = true,
+            "--diff" => options.diff = true,
+            "--patch" => options.patches.push(parse_patch(&value("--patch")?)?),
+            "--record" => options.record = Some(value("--record")?),
+            "--limit" => {
+                let limit = value("--limit")?;
+                options.limit = Some(limit.parse().map_err(|_| format!("Invalid limit '{}'", limit))?);
+            }
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}\n\n{}", arg, USAGE)),
+            _ => positional.push(arg),
+        }
+    }
+    if positional.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    options.program = positional.remove(0);
+    options.inputs = positional;
+    Ok(options)
+}
+
+fn parse_program(raw: &str) -> Result<Vec<i128>, String> {
+    raw.split(',')
+        .enumerate()
+        .map(|(i, s)| s.trim().parse().map_err(|_| format!("Invalid program value '{}' at address {}", s.trim(), i)))
+        .collect()
+}
+
+fn encode_input(line: &str, ascii: bool) -> Result<Vec<i128>, String> {
+    if ascii {
+        Ok(line.chars().chain(Some('\n')).map(|c| i128::from(u32::from(c))).collect())
+    } else {
+        line.trim().parse().map(|i| vec![i]).map_err(|_| format!("Invalid input '{}'", line.trim()))
+    }
+}
+
+fn print_output(out: &mut impl Write, value: i128, ascii: bool) -> io::Result<()> {
+    match u8::try_from(value) {
+        Ok(c) if ascii && c.is_ascii() => write!(out, "{}", char::from(c)),
+        _ => writeln!(out, "{}", value),
+    }
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let raw = fs::read_to_string(&options.program).map_err(|e| format!("Failed to read {}: {}", options.program, e))?;
+    if raw.trim().is_empty() {
+        return Err(format!("{} contains no program", options.program));
+    }
+    let mut program = parse_program(&raw)?;
+    let patches: Vec<Patch<i128>> = options.patches.iter()
+        .map(|&(address, old, new)| Patch::new("--patch", address, old, new))
+        .collect();
+    let mut vm = match &options.record {
+        Some(log) => IntcodeVm::recording(&program, &patches, Path::new(log)).map_err(|e| e.to_string())?,
+        None => IntcodeVm::patched(&program, &patches).map_err(|e| e.to_string())?,
+    };
+    apply_patches(&mut program, &patches).map_err(|e| e.to_string())?;
+    if options.trace {
+        patches.iter().for_each(|patch| eprintln!("patch {}", patch));
+    }
+
+    let mut inputs: VecDeque<String> = options.inputs.iter().cloned().collect();
+    let mut stdin = io::stdin().lock().lines();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut steps = 0;
+    loop {
+        if vm.needs_input() {
+            out.flush().map_err(|e| e.to_string())?;
+            let line = match inputs.pop_front() {
+                Some(line) => line,
+                None => match stdin.next() {
+                    Some(line) => line.map_err(|e| e.to_string())?,
+                    None => return Err(IntcodeError::InputClosed { ip: *vm.ip() }.to_string()),
+                },
+            };
+            encode_input(&line, options.ascii)?.into_iter().for_each(|i| vm.push_input(i));
+        }
+        if options.limit.is_some_and(|limit| steps >= limit) {
+            return Err(format!("Instruction limit {} reached at ip={}", steps, vm.ip()));
+        }
+        if options.trace {
+            eprintln!("{:>6} ip={} {}", steps, vm.ip(), vm.read(vm.ip()));
+        }
+        match vm.step().map_err(|e| e.to_string())? {
+            Some(Event::Output(value)) => print_output(&mut out, value, options.ascii).map_err(|e| e.to_string())?,
+            Some(Event::Halted) => break,
+            _ => {}
+        }
+        steps += 1;
+    }
+
+    let memory = vm.into_memory();
+    if options.dump {
+        write!(out, "{}", memory::format_dump(&memory)).map_err(|e| e.to_string())?;
+    }
+    if options.diff {
+        write!(out, "{}", memory::format_diff(&memory::diff_program(&program, &memory))).map_err(|e| e.to_string())?;
+    }
+    out.flush().map_err(|e| e.to_string())
+}
+
+fn main() {
+    if let Err(e) = parse_args(env::args().skip(1)).and_then(run) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> impl Iterator<Item = String> + '_ {
+        line.split_whitespace().map(String::from)
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(args("--ascii --patch 0=1:2 --record day17.log --limit 10 day17 A,B")).unwrap();
+        assert_eq!(options, Options {
+            program: "day17".to_string(),
+            inputs: vec!["A,B".to_string()],
+            patches: vec![(0, 1, 2)],
+            record: Some("day17.log".to_string()),
+            ascii: true,
+            limit: Some(10),
+            ..Options::default()
+        });
+        assert!(parse_args(args("")).is_err());
+        assert!(parse_args(args("--limit")).is_err());
+        assert!(parse_args(args("day17 --record")).is_err());
+        assert!(parse_args(args("--patch 0 day02")).is_err());
+        assert!(parse_args(args("--patch 0=2 day02")).is_err());
+        assert_eq!(parse_patch("1 = -3:12"), Ok((1, -3, 12)));
+        assert!(parse_args(args("--fast day02")).is_err());
+    }
+
+    #[test]
+    fn test_parse_program() {
+        assert_eq!(parse_program("1,0, -3,99\n"), Ok(vec![1, 0, -3, 99]));
+        assert_eq!(parse_program("1,0,x,99"), Err("Invalid program value 'x' at address 2".to_string()));
+        assert!(parse_program("1,,99").is_err());
+    }
+
+    #[test]
+    fn test_encode_input() {
+        assert_eq!(encode_input(" -5 ", false), Ok(vec![-5]));
+        assert!(encode_input("A", false).is_err());
+        assert_eq!(encode_input("A,1", true), Ok(vec![65, 44, 49, 10]));
+    }
+}
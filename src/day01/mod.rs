@@ -33,7 +33,7 @@ impl Solver for Day1Solver {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lib::test_solver;
+    use crate::test_solver;
 
     #[test]
     fn test_part_one() {
@@ -4,44 +4,37 @@ use std::sync::mpsc;
 
 use crate::lib::Solver;
 use crate::lib::intcode_computer;
+use crate::lib::intcode_computer::Patch;
+use crate::lib::intcode_computer::batch;
+use crate::lib::intcode_computer::inverse::{self, Target, Unknown};
 
 pub(crate) struct Day2Solver {}
 
+fn noun_verb_patches(noun: i128, verb: i128) -> [Patch<i128>; 2] {
+    [Patch::new("noun", 1, 0, noun), Patch::new("verb", 2, 0, verb)]
+}
+
 impl Solver for Day2Solver {
     fn solve(&self, lines: Vec<String>, part_two: bool) -> String {
         let orig_program: Vec<i128> = intcode_computer::read_program(&lines[0]);
 
         if !part_two {
-            let mut program = orig_program.clone();
-            program[1] = 12;
-            program[2] = 2;
             let (input_sender, input_receiver): (Sender<i128>, Receiver<i128>) = mpsc::channel();
             let (output_sender, _): (Sender<i128>, Receiver<i128>) = mpsc::channel();
             input_sender.send(0).ok();
-            let (_, memory ) = intcode_computer::run_program(input_receiver, output_sender, &mut program);
+            let (_, memory ) = intcode_computer::run_patched_program(input_receiver, output_sender, &orig_program, &noun_verb_patches(12, 2));
             return memory.get(&0).unwrap().to_string()
 
         }
 
-        for noun in 0..99 {
-            for verb in 0..99 {
-                let mut program = orig_program.clone();
-                program[1] = noun;
-                program[2] = verb;
-
-                let (input_sender, input_receiver): (Sender<i128>, Receiver<i128>) = mpsc::channel();
-                let (output_sender, _): (Sender<i128>, Receiver<i128>) = mpsc::channel();
-                input_sender.send(0).ok();
-                let (_, memory) = intcode_computer::run_program(input_receiver, output_sender, &mut program);
+        let unknowns = [Unknown::new("noun", 1, 0, 0..=99), Unknown::new("verb", 2, 0, 0..=99)];
+        let target = Target::Memory { address: 0, value: 19_690_720 };
+        let found = inverse::solve(&orig_program, &unknowns, &target, batch::default_workers()).unwrap_or_else(|e| panic!("{}", e));
 
-                if *memory.get(&0).unwrap() == 19_690_720 {
-                    return (noun * 100 + verb).to_string()
-                }
-
-            }
+        match found {
+            Some(solution) => (solution.values[0] * 100 + solution.values[1]).to_string(),
+            None => String::from("Fail")
         }
-
-        String::from("Fail")
     }
 }
 
@@ -49,45 +42,7 @@ impl Solver for Day2Solver {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lib::test_solver;
-
-
-    /*
-    fn test_program(input: &[&str], expected_answer: &str) {
-        let mut program: Vec<i128> = input[0]
-            .split(',')
-            .map(|s| s.parse::<i128>().unwrap())
-            .collect();
-
-        let (input_sender, input_receiver): (Sender<i128>, Receiver<i128>) = mpsc::channel();
-        let (output_sender, output_receiver): (Sender<i128>, Receiver<i128>) = mpsc::channel();
-        input_sender.send(0).ok();
-
-        let (_, memory) = intcode_computer::run_program(input_receiver, output_sender, &mut program);
-        let mut output_memory = Vec::with_capacity(memory.len());
-        for i in 0..memory.len() {
-            output_memory[i] = memory.get(&(i as i128)).unwrap();
-        }
-
-        let output = output_memory
-            .iter()
-            .map(|v| v.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-
-        assert_eq!(output, expected_answer);
-    }
-
-    #[test]
-    fn test_run_program() {
-        test_program(&["1,9,10,3,2,3,11,0,99,30,40,50"], "3500,9,10,70,2,3,11,0,99,30,40,50");
-        test_program(&["1,0,0,0,99"], "2,0,0,0,99");
-        test_program( &["2,3,0,3,99"], "2,3,0,6,99");
-        test_program( &["2,4,4,5,99,0"], "2,4,4,5,99,9801");
-        test_program( &["1,1,1,4,99,5,6,0,99"], "30,1,1,4,2,5,6,0,99");
-    }
-    */
-
+    use crate::test_solver;
 
     #[test]
     fn test_part_one() {
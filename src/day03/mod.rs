@@ -3,6 +3,7 @@
 use std::collections::HashMap;
 
 use crate::lib::{Solver, Position, manhattan_distance};
+use crate::lib::direction::Direction;
 
 fn create_map(parts: Vec<&str>) -> HashMap<Position, i32> {
     let mut curr = Position{x: 0, y: 0};
@@ -13,15 +14,10 @@ fn create_map(parts: Vec<&str>) -> HashMap<Position, i32> {
     for part in parts {
         let (dir, number) = part.split_at(1);
         let steps: i32 = number.parse().unwrap();
-        let step = match dir {
-            "R" => Position::right,
-            "L" => Position::left,
-            "U" => Position::up,
-            "D" => Position::down,
-            _ => panic!("Invalid direction! ".to_owned() + dir),
-        };
+        let direction = dir.chars().next().and_then(Direction::from_letter)
+            .unwrap_or_else(|| panic!("Invalid direction! {}", dir));
         for _ in 0..steps {
-            curr = step(&curr);
+            curr = direction.step(&curr);
             nbr_steps =  nbr_steps+1;
             if !line.contains_key(&curr) {
                 line.insert(curr, nbr_steps);
@@ -64,7 +60,7 @@ impl Solver for Day3Solver {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lib::test_solver;
+    use crate::test_solver;
 
 
     #[test]
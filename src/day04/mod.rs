@@ -65,7 +65,7 @@ impl Solver for Day4Solver {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lib::test_solver;
+    use crate::test_solver;
 
 
     #[test]
@@ -31,7 +31,7 @@ impl Solver for Day5Solver {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lib::test_solver;
+    use crate::test_solver;
 
     fn test_program(input: i128, program_raw: &[&str], expected_output: i128) {
         let mut program: Vec<i128> = program_raw[0]
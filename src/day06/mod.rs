@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use crate::lib::Solver;
+use crate::lib::search::bfs;
 
 pub(crate) struct Day6Solver {}
 
@@ -38,27 +39,23 @@ impl Solver for Day6Solver {
             return total_orbits.to_string();
         }
 
-        let santa_way = calculate_orbits(&"SAN".to_string(), &String::from("COM"),&galaxy_map);
-        let you_way = calculate_orbits(&"YOU".to_string(), &String::from("COM"), &galaxy_map);
-
-        for k in santa_way.iter() {
-            if you_way.contains(k) {
-
-                let jumps_for_santa = calculate_orbits(&"SAN".to_string(), k,&galaxy_map).len() - 2;
-                let jumps_for_you = calculate_orbits(&"YOU".to_string(), k,&galaxy_map).len() - 2;
-                println!("Common {} santa={} you={}", k, jumps_for_santa, jumps_for_you);
-                return (jumps_for_santa + jumps_for_you).to_string();
-            }
+        let mut graph: HashMap<&String, Vec<&String>> = HashMap::new();
+        for (satellite, center) in galaxy_map.iter() {
+            graph.entry(center).or_default().push(satellite);
+            graph.entry(satellite).or_default().push(center);
         }
+        let you_center = &galaxy_map["YOU"];
+        let santa_center = &galaxy_map["SAN"];
+        let transfers = bfs(you_center, |n| graph[n].clone(), |n| *n == santa_center);
 
-        return String::from("Fail");
+        return transfers.distance(&santa_center).map_or(String::from("Fail"), |d| d.to_string());
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lib::test_solver;
+    use crate::test_solver;
 
     #[test]
     fn test_part_one() {
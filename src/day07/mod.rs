@@ -1,106 +1,58 @@
-use std::sync::mpsc::{Sender, Receiver};
-use std::sync::mpsc;
-use std::thread;
-
 use crate::lib::Solver;
-use crate::lib::intcode_computer;
+use crate::lib::intcode_computer::{self, Event, IntcodeVm};
+use crate::lib::intcode_computer::batch;
 
 pub(crate) struct Day7Solver {}
 
-fn valid(a: i128, b: i128, c: i128, d: i128, e: i128) -> bool {
-    a != b && a != c && a != d && a != e &&
-        b != c && b != d && b != e &&
-        c != d && c != e &&
-        d != e
+fn permutations(values: &[i128]) -> Vec<Vec<i128>> {
+    if values.is_empty() {
+        return vec![vec![]];
+    }
+    let mut result = Vec::new();
+    for i in 0..values.len() {
+        let mut rest = values.to_vec();
+        let first = rest.remove(i);
+        for mut permutation in permutations(&rest) {
+            permutation.insert(0, first);
+            result.push(permutation);
+        }
+    }
+    result
 }
 
-impl Solver for Day7Solver {
-    fn solve(&self, lines: Vec<String>, part_two: bool) -> String {
-        let orig_program = intcode_computer::read_program(&lines[0]);
-
-        let mut max: i128 = 0;
-        let low_bound: i128 = if !part_two { 0 } else { 5 };
-        let upper_bound = if !part_two { 4 } else { 9 };
-
-
-        for a in low_bound..=upper_bound {
-            for b in low_bound..=upper_bound {
-                for c in low_bound..=upper_bound {
-                    for d in low_bound..=upper_bound {
-                        for e in low_bound..=upper_bound {
-                            if valid(a, b, c, d, e) {
-                                let mut program_a = orig_program.clone();
-                                let mut program_b = orig_program.clone();
-                                let mut program_c = orig_program.clone();
-                                let mut program_d = orig_program.clone();
-                                let mut program_e = orig_program.clone();
-                                let (a_input_sender, a_input_receiver): (Sender<i128>, Receiver<i128>) = mpsc::channel();
-                                let (b_input_sender, b_input_receiver): (Sender<i128>, Receiver<i128>) = mpsc::channel();
-                                let (c_input_sender, c_input_receiver): (Sender<i128>, Receiver<i128>) = mpsc::channel();
-                                let (d_input_sender, d_input_receiver): (Sender<i128>, Receiver<i128>) = mpsc::channel();
-                                let (e_input_sender, e_input_receiver): (Sender<i128>, Receiver<i128>) = mpsc::channel();
-                                let (output_sender, output_receiver): (Sender<i128>, Receiver<i128>) = mpsc::channel();
-
-                                a_input_sender.send(a).ok();
-                                b_input_sender.send(b).ok();
-                                c_input_sender.send(c).ok();
-                                d_input_sender.send(d).ok();
-                                e_input_sender.send(e).ok();
-
-                                let ah = thread::spawn(move || {
-                                    intcode_computer::run_program(a_input_receiver, b_input_sender, &mut program_a)
-                                });
-
-                                let bh = thread::spawn(move || {
-                                    intcode_computer::run_program(b_input_receiver, c_input_sender, &mut program_b)
-                                });
-
-                                let ch = thread::spawn(move || {
-                                    intcode_computer::run_program(c_input_receiver, d_input_sender, &mut program_c)
-                                });
-
-                                let dh = thread::spawn(move || {
-                                    intcode_computer::run_program(d_input_receiver, e_input_sender, &mut program_d)
-                                });
-
-                                let eh = thread::spawn(move || {
-                                    intcode_computer::run_program(e_input_receiver, output_sender, &mut program_e)
-                                });
-
-                                if !part_two {
-                                    a_input_sender.send(0).ok();
-                                    let out = output_receiver.recv().unwrap();
-                                    if out > max {
-                                        max = out;
-                                    }
-                                } else {
-                                    thread::spawn(move || {
-                                        a_input_sender.send(0).ok();
-                                        loop {
-                                            let out_result = output_receiver.recv();
-                                            if out_result.is_ok() {
-                                                a_input_sender.send(out_result.unwrap()).ok();
-                                            }
-                                        }
-                                    });
-
-                                    let out = vec![ah, bh, ch, dh, eh].into_iter().map(|h| {
-                                        let (io, _) = h.join().unwrap();
-                                        io
-                                    }).last().unwrap();
-
-                                    if out > max {
-                                        max = out;
-                                    }
-                                }
-
-                            }
-                        }
-                    }
-                }
+fn run_amplifiers(program: &[i128], phases: &[i128]) -> i128 {
+    let mut amplifiers: Vec<IntcodeVm> = phases.iter()
+        .map(|&phase| {
+            let mut vm = IntcodeVm::new(program);
+            vm.push_input(phase);
+            vm
+        })
+        .collect();
+
+    let mut signal = 0;
+    loop {
+        for amplifier in amplifiers.iter_mut() {
+            amplifier.push_input(signal);
+            match amplifier.run_until_event().unwrap_or_else(|e| panic!("{}", e)) {
+                Event::Output(o) => signal = o,
+                Event::Halted => return signal,
+                Event::NeedInput => panic!("Amplifier stuck waiting for input"),
             }
         }
-        return max.to_string();
+    }
+}
+
+impl Solver for Day7Solver {
+    fn solve(&self, lines: Vec<String>, part_two: bool) -> String {
+        let program = intcode_computer::read_program(&lines[0]);
+        let phases: Vec<i128> = if !part_two { (0..=4).collect() } else { (5..=9).collect() };
+        let settings = permutations(&phases);
+
+        batch::parallel_map(&settings, batch::default_workers(), |phases| run_amplifiers(&program, phases))
+            .into_iter()
+            .max()
+            .unwrap()
+            .to_string()
     }
 }
 
@@ -108,10 +60,9 @@ impl Solver for Day7Solver {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lib::test_solver;
+    use crate::test_solver;
 
     #[test]
-    #[ignore]
     fn test_part_one() {
         let solver = Day7Solver {};
         test_solver(&solver, false, &["3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0"], "43210");
@@ -120,7 +71,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore]
     fn test_part_two() {
         let solver = Day7Solver {};
         test_solver(&solver, true, &["3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5"], "139629729");
@@ -1,31 +1,19 @@
-use std::sync::mpsc;
-
 use crate::lib::Solver;
-use crate::lib::intcode_computer;
+use crate::lib::intcode_computer::{self, IntcodeVm};
 
 pub(crate) struct Day9Solver {}
 
 impl Solver for Day9Solver {
     fn solve(&self, lines: Vec<String>, part_two: bool) -> String {
-        let mut program = intcode_computer::read_program(&lines[0]);
-        let (input_sender, input_receiver) = mpsc::channel();
-        let (output_sender, output_receiver) = mpsc::channel();
-
+        let program: Vec<i128> = intcode_computer::read_program(&lines[0]);
         let input = if !part_two { 1 } else { 2 };
 
-        input_sender.send(input).ok();
-        intcode_computer::run_program(input_receiver, output_sender, &mut program);
-
         let mut latest = 0;
-        loop {
-            match output_receiver.recv() {
-                Ok(o) => {
-                    latest = o;
-                    println!("Output={}", o)
-                }
-                _ => return latest.to_string()
-            }
+        for o in IntcodeVm::new(&program).with_inputs([input]) {
+            latest = o;
+            println!("Output={}", o)
         }
+        latest.to_string()
     }
 }
 
@@ -33,7 +21,7 @@ impl Solver for Day9Solver {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lib::test_solver;
+    use crate::test_solver;
 
     #[test]
     fn test_part_one() {
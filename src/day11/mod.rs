@@ -1,82 +1,26 @@
-use std::collections::HashMap;
-use std::sync::mpsc;
-use std::thread;
-
-use crate::day11::Direction::{DOWN, LEFT, RIGHT, UP};
 use crate::lib::{intcode_computer, Position, Solver};
+use crate::lib::direction::Direction;
+use crate::lib::grid::{Bounds, Grid};
+use crate::lib::image::{self, Image, BLACK, WHITE};
+use crate::lib::ocr;
+use crate::lib::intcode_computer::IntcodeVm;
 
 pub(crate) struct Day11Solver {}
 
-enum Direction {
-    UP,
-    LEFT,
-    RIGHT,
-    DOWN,
-}
-
 struct PaintRobot {
     pub position: Position,
     pub orientation: Direction,
 }
 
-fn turn_left(dir: &Direction) -> Direction {
-    return match dir {
-        UP => LEFT,
-        LEFT => DOWN,
-        RIGHT => UP,
-        DOWN => RIGHT,
-    }
-}
-
-fn turn_right(dir: &Direction) -> Direction {
-    return match dir {
-        UP => RIGHT,
-        LEFT => UP,
-        RIGHT => DOWN,
-        DOWN => LEFT,
-    }
-}
-
-fn move_bot(bot: &PaintRobot) -> Position {
-    return match bot.orientation {
-        UP => bot.position.up(),
-        LEFT => bot.position.left(),
-        RIGHT => bot.position.right(),
-        DOWN => bot.position.down(),
-    }
-}
-
-fn print_state(bot: &PaintRobot, painting: &HashMap<Position, bool>) -> String {
-    let mut min_x = bot.position.x;
-    let mut min_y = bot.position.y;
-    let mut max_x = bot.position.x;
-    let mut max_y = bot.position.y;
-    for (p, _) in painting.iter() {
-        if p.x < min_x {
-            min_x = p.x;
-        }
-        if p.x > max_x {
-            max_x = p.x;
-        }
-        if p.y < min_y {
-            min_y = p.y;
-        }
-        if p.y > max_y {
-            max_y = p.y;
-        }
-    }
+fn print_state(bot: &PaintRobot, painting: &Grid<bool>) -> String {
+    let mut bounds = painting.bounds().unwrap_or_else(|| Bounds::new(bot.position));
+    bounds.extend(bot.position);
     let mut output = String::new();
     output.push_str("\n");
-    for y in (min_y..(max_y + 1)).rev() {
-        for x in min_x..(max_x + 1) {
+    for y in (bounds.min.y..(bounds.max.y + 1)).rev() {
+        for x in bounds.min.x..(bounds.max.x + 1) {
             if bot.position.x == x && bot.position.y == y {
-                let char_to_print = match bot.orientation {
-                    UP => '^',
-                    LEFT => '<',
-                    RIGHT => '>',
-                    DOWN => 'v',
-                };
-                output.push(char_to_print)
+                output.push(bot.orientation.arrow())
             } else {
                 let is_white = painting.get(&Position{x, y }).unwrap_or(&false);
                 if *is_white {
@@ -94,61 +38,51 @@ fn print_state(bot: &PaintRobot, painting: &HashMap<Position, bool>) -> String {
 
 impl Solver for Day11Solver {
     fn solve(&self, lines: Vec<String>, part_two: bool) -> String {
-        let mut program = intcode_computer::read_program(&lines[0]);
-        let (input_sender, input_receiver) = mpsc::channel();
-        let (output_sender, output_receiver) = mpsc::channel();
-        if part_two {
-            input_sender.send(1).ok();
-        } else {
-            input_sender.send(0).ok();
-        }
-        thread::spawn(move || {
-            intcode_computer::run_program(input_receiver, output_sender, &mut program);
-        });
-
-        let mut painting = HashMap::new();
-        let mut bot = PaintRobot { position: Position { x: 0, y: 0 }, orientation: UP };
-        let mut painting_mode = true;
-        loop {
-            match output_receiver.recv() {
-                Ok(o) => {
-                    if painting_mode {
-                        let painting_white = if o == 0 {
-                            false
-                        } else if o == 1 {
-                            true
-                        } else {
-                            panic!("Unexpected input!")
-                        };
-                        painting.insert(bot.position, painting_white);
-                        painting_mode = false;
-                    } else {
-                        if o == 0 {
-                            bot.orientation = turn_left(&bot.orientation)
-                        } else if o == 1 {
-                            bot.orientation = turn_right(&bot.orientation)
-                        }
-                        bot.position = move_bot(&bot);
-                        let is_white = painting.get(&bot.position).unwrap_or(&false);
-                        if *is_white {
-                            input_sender.send(1).ok();
-                        } else {
-                            input_sender.send(0).ok();
-                        }
-
-                        painting_mode = true;
-                    }
-                }
-                _ => return if part_two { print_state(&bot, &painting) } else { painting.len().to_string() }
+        let program: Vec<i128> = intcode_computer::read_program(&lines[0]);
+        let start = if part_two { 1 } else { 0 };
+        let mut robot = IntcodeVm::new(&program).with_inputs([start]).chunks(2);
+
+        let mut painting = Grid::sparse();
+        let mut bot = PaintRobot { position: Position { x: 0, y: 0 }, orientation: Direction::Up };
+        while let Some(o) = robot.next() {
+            let painting_white = if o[0] == 0 {
+                false
+            } else if o[0] == 1 {
+                true
+            } else {
+                panic!("Unexpected input!")
+            };
+            painting.insert(bot.position, painting_white);
+            if o[1] == 0 {
+                bot.orientation = bot.orientation.turn_left()
+            } else if o[1] == 1 {
+                bot.orientation = bot.orientation.turn_right()
+            }
+            bot.position = bot.orientation.step(&bot.position);
+            let is_white = painting.get(&bot.position).unwrap_or(&false);
+            if *is_white {
+                robot.push_input(1);
+            } else {
+                robot.push_input(0);
             }
         }
+        if !part_two {
+            return painting.len().to_string();
+        }
+        let picture = Image::from_grid(&painting, |white| if white == Some(&true) { WHITE } else { BLACK }, 10);
+        if let Err(e) = image::export("day11", &picture.flip_vertical()) {
+            println!("Failed to write image: {}", e);
+        }
+        println!("{}", print_state(&bot, &painting));
+        let letters = painting.display(|white| if white == Some(&true) { '#' } else { '.' }).y_up().to_string();
+        ocr::recognize_str(&letters, '#').unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lib::test_solver;
+    use crate::test_solver;
 
     #[test]
     fn test_part_one() {
@@ -159,7 +93,7 @@ mod tests {
     #[test]
     fn test_part_two() {
         let solver = Day11Solver {};
-        test_solver(&solver, true, &["3,8,1005,8,306,1106,0,11,0,0,0,104,1,104,0,3,8,1002,8,-1,10,1001,10,1,10,4,10,108,1,8,10,4,10,1002,8,1,28,2,107,3,10,1,101,19,10,3,8,1002,8,-1,10,1001,10,1,10,4,10,1008,8,0,10,4,10,102,1,8,59,2,5,13,10,3,8,102,-1,8,10,1001,10,1,10,4,10,1008,8,0,10,4,10,1001,8,0,85,3,8,1002,8,-1,10,101,1,10,10,4,10,1008,8,1,10,4,10,1001,8,0,107,1006,0,43,3,8,1002,8,-1,10,1001,10,1,10,4,10,1008,8,1,10,4,10,101,0,8,132,3,8,102,-1,8,10,1001,10,1,10,4,10,1008,8,0,10,4,10,1001,8,0,154,2,4,1,10,2,4,9,10,3,8,1002,8,-1,10,101,1,10,10,4,10,108,0,8,10,4,10,1001,8,0,183,1,1102,5,10,1,1102,1,10,1006,0,90,2,9,12,10,3,8,102,-1,8,10,1001,10,1,10,4,10,1008,8,0,10,4,10,1001,8,0,221,1006,0,76,1006,0,27,1,102,9,10,3,8,1002,8,-1,10,1001,10,1,10,4,10,108,1,8,10,4,10,102,1,8,252,2,4,9,10,1006,0,66,3,8,1002,8,-1,10,101,1,10,10,4,10,1008,8,1,10,4,10,101,0,8,282,1,102,19,10,101,1,9,9,1007,9,952,10,1005,10,15,99,109,628,104,0,104,1,21102,1,387240010644,1,21101,0,323,0,1105,1,427,21102,846541370112,1,1,21101,334,0,0,1106,0,427,3,10,104,0,104,1,3,10,104,0,104,0,3,10,104,0,104,1,3,10,104,0,104,1,3,10,104,0,104,0,3,10,104,0,104,1,21102,3425718295,1,1,21102,381,1,0,1105,1,427,21102,179410541715,1,1,21101,0,392,0,1106,0,427,3,10,104,0,104,0,3,10,104,0,104,0,21101,0,718078255872,1,21101,0,415,0,1105,1,427,21102,1,868494234468,1,21102,1,426,0,1105,1,427,99,109,2,21202,-1,1,1,21101,0,40,2,21101,458,0,3,21101,0,448,0,1106,0,491,109,-2,2106,0,0,0,1,0,0,1,109,2,3,10,204,-1,1001,453,454,469,4,0,1001,453,1,453,108,4,453,10,1006,10,485,1102,0,1,453,109,-2,2105,1,0,0,109,4,2102,1,-1,490,1207,-3,0,10,1006,10,508,21102,1,0,-3,22102,1,-3,1,22101,0,-2,2,21102,1,1,3,21102,1,527,0,1106,0,532,109,-4,2105,1,0,109,5,1207,-3,1,10,1006,10,555,2207,-4,-2,10,1006,10,555,22101,0,-4,-4,1105,1,623,22101,0,-4,1,21201,-3,-1,2,21202,-2,2,3,21101,574,0,0,1105,1,532,21202,1,1,-4,21102,1,1,-1,2207,-4,-2,10,1006,10,593,21102,0,1,-1,22202,-2,-1,-2,2107,0,-3,10,1006,10,615,21201,-1,0,1,21101,615,0,0,106,0,490,21202,-2,-1,-2,22201,-4,-2,-4,109,-5,2105,1,0"], "\n.  .  #  #  .  .  .  #  #  .  .  #  #  #  .  .  #  #  #  .  .  #  .  .  #  .  #  #  #  #  .  #  .  .  #  .  #  .  .  .  .  .  .  \n.  #  .  .  #  .  #  .  .  #  .  #  .  .  #  .  #  .  .  #  .  #  .  #  .  .  .  .  .  #  .  #  .  .  #  .  #  .  .  .  .  .  .  \n.  #  .  .  .  .  #  .  .  #  .  #  .  .  #  .  #  .  .  #  .  #  #  .  .  .  .  .  #  .  .  #  .  .  #  .  #  .  .  .  .  .  .  \n.  #  .  #  #  .  #  #  #  #  .  #  #  #  .  .  #  #  #  .  .  #  .  #  .  .  .  #  .  .  .  #  .  .  #  .  #  .  .  .  .  .  .  \n.  #  .  .  #  .  #  .  .  #  .  #  .  #  .  .  #  .  .  .  .  #  .  #  .  .  #  .  .  .  .  #  .  .  #  .  #  .  .  .  .  >  .  \n.  .  #  #  #  .  #  .  .  #  .  #  .  .  #  .  #  .  .  .  .  #  .  .  #  .  #  #  #  #  .  .  #  #  .  .  #  #  #  #  .  .  .  \n");
+        test_solver(&solver, true, &["3,8,1005,8,306,1106,0,11,0,0,0,104,1,104,0,3,8,1002,8,-1,10,1001,10,1,10,4,10,108,1,8,10,4,10,1002,8,1,28,2,107,3,10,1,101,19,10,3,8,1002,8,-1,10,1001,10,1,10,4,10,1008,8,0,10,4,10,102,1,8,59,2,5,13,10,3,8,102,-1,8,10,1001,10,1,10,4,10,1008,8,0,10,4,10,1001,8,0,85,3,8,1002,8,-1,10,101,1,10,10,4,10,1008,8,1,10,4,10,1001,8,0,107,1006,0,43,3,8,1002,8,-1,10,1001,10,1,10,4,10,1008,8,1,10,4,10,101,0,8,132,3,8,102,-1,8,10,1001,10,1,10,4,10,1008,8,0,10,4,10,1001,8,0,154,2,4,1,10,2,4,9,10,3,8,1002,8,-1,10,101,1,10,10,4,10,108,0,8,10,4,10,1001,8,0,183,1,1102,5,10,1,1102,1,10,1006,0,90,2,9,12,10,3,8,102,-1,8,10,1001,10,1,10,4,10,1008,8,0,10,4,10,1001,8,0,221,1006,0,76,1006,0,27,1,102,9,10,3,8,1002,8,-1,10,1001,10,1,10,4,10,108,1,8,10,4,10,102,1,8,252,2,4,9,10,1006,0,66,3,8,1002,8,-1,10,101,1,10,10,4,10,1008,8,1,10,4,10,101,0,8,282,1,102,19,10,101,1,9,9,1007,9,952,10,1005,10,15,99,109,628,104,0,104,1,21102,1,387240010644,1,21101,0,323,0,1105,1,427,21102,846541370112,1,1,21101,334,0,0,1106,0,427,3,10,104,0,104,1,3,10,104,0,104,0,3,10,104,0,104,1,3,10,104,0,104,1,3,10,104,0,104,0,3,10,104,0,104,1,21102,3425718295,1,1,21102,381,1,0,1105,1,427,21102,179410541715,1,1,21101,0,392,0,1106,0,427,3,10,104,0,104,0,3,10,104,0,104,0,21101,0,718078255872,1,21101,0,415,0,1105,1,427,21102,1,868494234468,1,21102,1,426,0,1105,1,427,99,109,2,21202,-1,1,1,21101,0,40,2,21101,458,0,3,21101,0,448,0,1106,0,491,109,-2,2106,0,0,0,1,0,0,1,109,2,3,10,204,-1,1001,453,454,469,4,0,1001,453,1,453,108,4,453,10,1006,10,485,1102,0,1,453,109,-2,2105,1,0,0,109,4,2102,1,-1,490,1207,-3,0,10,1006,10,508,21102,1,0,-3,22102,1,-3,1,22101,0,-2,2,21102,1,1,3,21102,1,527,0,1106,0,532,109,-4,2105,1,0,109,5,1207,-3,1,10,1006,10,555,2207,-4,-2,10,1006,10,555,22101,0,-4,-4,1105,1,623,22101,0,-4,1,21201,-3,-1,2,21202,-2,2,3,21101,574,0,0,1105,1,532,21202,1,1,-4,21102,1,1,-1,2207,-4,-2,10,1006,10,593,21102,0,1,-1,22202,-2,-1,-2,2107,0,-3,10,1006,10,615,21201,-1,0,1,21101,615,0,0,106,0,490,21202,-2,-1,-2,22201,-4,-2,-4,109,-5,2105,1,0"], "GARPKZUL");
     }
 
 
@@ -1,25 +1,12 @@
 use std::collections::HashMap;
-use std::sync::mpsc;
-use std::thread;
+use std::convert::TryFrom;
 
 use crate::lib::{intcode_computer, Position, Solver};
+use crate::lib::intcode_computer::Patch;
+use crate::intcode_vm;
 
 pub(crate) struct Day13Solver {}
 
-enum State {
-    X,
-    Y,
-    TileId
-}
-
-fn next_state(state: State) -> State {
-    return match state {
-        State::X => State::Y,
-        State::Y => State::TileId,
-        State::TileId => State::X,
-    }
-}
-
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
 enum TileType {
     Empty,
@@ -54,68 +41,44 @@ fn get_next_move(paddle_x: i32, ball_x: i32) -> i32 {
 
 impl Solver for Day13Solver {
     fn solve(&self, lines: Vec<String>, part_two: bool) -> String {
-        let mut program = intcode_computer::read_program(&lines[0]);
-        if part_two {
-            program[0] = 2;
-        }
+        let program = intcode_computer::read_program(&lines[0]);
+        let patches = if part_two { vec![Patch::new("insert quarters", 0, 1, 2)] } else { vec![] };
 
-        let (input_sender, input_receiver) = mpsc::channel();
-        let (output_sender, output_receiver) = mpsc::channel();
-        thread::spawn(move || {
-            intcode_computer::run_program(input_receiver, output_sender, &mut program);
-        });
+        let mut game = intcode_vm(&program, &patches).with_inputs([0]).chunks(3);
 
-        let mut state = State::X;
-        let mut tmp_x: i32 = -1;
-        let mut tmp_y: i32 = -1;
         let mut tmp_tile_type: TileType;
         let mut screen = HashMap::new();
         let mut score: i32 = 0;
         let mut paddle: Tile = Tile{x: -1, y: -1, tile_type: TileType::HorizontalPaddle};
         let mut ball: Tile;
-        input_sender.send(0).ok();
-        loop {
-            // print_state(&bot, &painting);
-            match output_receiver.recv() {
-                Ok(o) => {
-                    match state {
-                        State::X => {
-                            tmp_x = o as i32;
-                        },
-                        State::Y => {
-                            tmp_y = o as i32;
-                        },
-                        State::TileId => {
-                            if tmp_x != -1 && tmp_y != 0 {
-                                match o {
-                                    0 => { tmp_tile_type = TileType::Empty; }
-                                    1 => { tmp_tile_type = TileType::Wall; }
-                                    2 => { tmp_tile_type = TileType::Block; }
-                                    3 => { tmp_tile_type = TileType::HorizontalPaddle; }
-                                    4 => { tmp_tile_type = TileType::Ball; }
-                                    _ => { panic!("Unexpected tile id!") }
-                                }
-                                let t = Tile{x: tmp_x, y: tmp_y, tile_type: tmp_tile_type};
-                                screen.insert(Position{ x: tmp_x, y: tmp_y },  t);
-                                if tmp_tile_type == TileType::HorizontalPaddle {
-                                    paddle = t.clone();
-                                } else if tmp_tile_type == TileType::Ball {
-                                    ball = t.clone();
-                                    if paddle.x != -1 {
-                                        let next_move = get_next_move(paddle.x, ball.x);
-                                        input_sender.send(next_move as i128).ok();
-                                    }
-                                }
-                            } else {
-                                score = o as i32;
-                            }
-                        },
+        while let Some(o) = game.next() {
+            let tmp_x = i32::try_from(o[0]).expect("X out of range");
+            let tmp_y = i32::try_from(o[1]).expect("Y out of range");
+            if tmp_x != -1 && tmp_y != 0 {
+                match o[2] {
+                    0 => { tmp_tile_type = TileType::Empty; }
+                    1 => { tmp_tile_type = TileType::Wall; }
+                    2 => { tmp_tile_type = TileType::Block; }
+                    3 => { tmp_tile_type = TileType::HorizontalPaddle; }
+                    4 => { tmp_tile_type = TileType::Ball; }
+                    _ => { panic!("Unexpected tile id!") }
+                }
+                let t = Tile{x: tmp_x, y: tmp_y, tile_type: tmp_tile_type};
+                screen.insert(Position{ x: tmp_x, y: tmp_y },  t);
+                if tmp_tile_type == TileType::HorizontalPaddle {
+                    paddle = t.clone();
+                } else if tmp_tile_type == TileType::Ball {
+                    ball = t.clone();
+                    if paddle.x != -1 {
+                        let next_move = get_next_move(paddle.x, ball.x);
+                        game.push_input(i128::from(next_move));
                     }
-                    state = next_state(state);
                 }
-                _ => return if !part_two { calculate_block_tiles(screen).to_string() } else { score.to_string() }
+            } else {
+                score = i32::try_from(o[2]).expect("Score out of range");
             }
         }
+        if !part_two { calculate_block_tiles(screen).to_string() } else { score.to_string() }
     }
 }
 
@@ -123,7 +86,7 @@ impl Solver for Day13Solver {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lib::test_solver;
+    use crate::test_solver;
 
 
     #[test]
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use crate::lib::Solver;
+use crate::lib::bisect::find_max_satisfying;
 
 #[derive(PartialEq, Eq, Hash, Debug, Clone)]
 struct Reaction {
@@ -126,36 +127,15 @@ impl Solver for Day14Solver {
         }
 
         let target = 1000000000000;
-        let mut guess = target / ore_needed;
-        let mut min = guess / 2;
-        let mut max = guess * 2;
-        let mut old_guess = -1;
-        let mut it = 0;
-        loop {
-            let ore_needed = calculate_ore(guess, start, &reaction_lookup);
-            if ore_needed < target {
-                min = guess;
-            } else if ore_needed > target {
-                max = guess;
-            }
-            guess = (min + max) / 2;
-            if guess == old_guess {
-                break;
-            }
-            old_guess = guess;
-            it += 1;
-            if it > 100 {
-                break;
-            }
-        }
-        return guess.to_string();
+        let fuel = find_max_satisfying(0, |fuel| calculate_ore(fuel, start, &reaction_lookup) <= target).unwrap();
+        return fuel.to_string();
     }
 }
 
 
 #[cfg(test)]
 mod tests {
-    use crate::lib::test_solver;
+    use crate::test_solver;
 
     use super::*;
 
@@ -1,11 +1,12 @@
 
 
 use crate::lib::{intcode_computer, Position, Solver};
-use std::sync::mpsc;
-use std::thread;
-use std::collections::{HashMap, VecDeque, HashSet};
-use crate::day15::LocationType::{Empty, Wall, OxygenSystem, Oxygen};
-use crate::day15::Direction::{NORTH, WEST, EAST, SOUTH};
+use crate::lib::direction::Direction;
+use crate::lib::intcode_computer::explore::{explore, Step, Strategy};
+use crate::lib::flood::flood_fill;
+use crate::intcode_vm;
+use std::collections::HashMap;
+use crate::day15::LocationType::{Empty, Wall, OxygenSystem};
 
 pub(crate) struct Day15Solver {}
 
@@ -14,7 +15,6 @@ enum LocationType {
     Empty,
     Wall,
     OxygenSystem,
-    Oxygen,
 }
 
 fn create_location_type(i: i128) -> LocationType {
@@ -26,216 +26,49 @@ fn create_location_type(i: i128) -> LocationType {
     }
 }
 
-#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
-enum Direction {
-    NORTH,
-    WEST,
-    EAST,
-    SOUTH
-}
-
-impl Direction {
-    fn get_command(&self) -> i32 {
-        return match self {
-            NORTH => 1,
-            WEST => 3,
-            EAST => 4,
-            SOUTH => 2,
-        }
-    }
-}
-
-fn get_neighbours(position: &Position, map: &HashMap<Position, LocationType>) -> Vec<Position> {
-    let mut neighbours = Vec::new();
-    for d in vec![NORTH, WEST, EAST, SOUTH] {
-        let n = match d {
-            NORTH => position.up(),
-            WEST => position.left(),
-            EAST => position.right(),
-            SOUTH => position.down(),
-        };
-        if map.contains_key(&n) {
-            let location_type = map.get(&n).unwrap();
-            match location_type {
-                Empty => neighbours.push(n),
-                OxygenSystem => neighbours.push(n),
-                _ => {},
-            }
-        } else {
-            neighbours.push(n)
-        }
-    }
-    return neighbours;
-}
-
-fn get_direction(p1: &Position, p2: &Position) -> Direction {
-    return if p1.x != p2.x {
-        if p1.x < p2.x {
-            EAST
-        } else {
-            WEST
-        }
-    } else {
-        if p1.y < p2.y {
-            NORTH
-        } else {
-            SOUTH
-        }
-    }
-}
-
-fn reconstruct_path(goal: &Position, from: &HashMap<Position, Position>) -> Vec<Direction> {
-    let mut path = Vec::new();
-    path.push(goal);
-    let mut curr = goal;
-    while from.contains_key(&curr) {
-        curr = from.get(curr).unwrap();
-        path.push(curr);
+fn get_command(direction: &Direction) -> i128 {
+    match direction {
+        Direction::Up => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+        Direction::Right => 4,
     }
-    path.reverse();
-    let mut directions = Vec::new();
-    for i in 0..(path.len()-1) {
-        let p1 = path[i];
-        let p2 = path[i+1];
-        let dir = get_direction(p1,p2);
-        directions.push(dir);
-    }
-    return directions;
-}
-
-fn find_min<'a>(f_score: &HashMap<Position, i32>, set: &'a HashSet<Position>) -> Option<&'a Position> {
-    return set.into_iter()
-        .min_by(|p1, p2| {
-            let i1 = f_score.get(p1).unwrap_or(&10_000);
-            let i2 = f_score.get(p2).unwrap_or(&10_000);
-            return i1.cmp(i2);}
-        );
-}
-
-fn search(position: Position, map: &HashMap<Position, LocationType>, goal_function: &dyn Fn(&HashMap<Position, LocationType>, &Position) -> bool) -> Vec<Direction> {
-    let h = |_: Position| 1;
-    let mut open_set = HashSet::new();
-    open_set.insert(position);
-    let mut came_from:HashMap<Position, Position> = HashMap::new();
-    let mut g_score: HashMap<Position, i32> = HashMap::new();
-    g_score.insert(position, 0);
-    let mut f_score: HashMap<Position, i32> = HashMap::new();
-    f_score.insert(position, h(position));
-
-    loop {
-        let current_opt = find_min(&f_score, &open_set);
-        if current_opt.is_none() {
-            break;
-        }
-        let current = *current_opt.unwrap();
-        if goal_function(map, &current) {
-            return reconstruct_path(&current, &came_from);
-        }
-        open_set.remove(&current);
-        for neighbour in get_neighbours(&current, &map) {
-            let tentative_g_score = g_score.get(&current).unwrap_or(&10_000) + 1;
-            let neighbour_g_score =  *g_score.get(&neighbour).unwrap_or(&10_000);
-            if tentative_g_score < neighbour_g_score {
-                came_from.insert(neighbour, current);
-                g_score.insert(neighbour, tentative_g_score);
-                f_score.insert(neighbour, neighbour_g_score + h(neighbour));
-                if !open_set.contains(&neighbour) {
-                    open_set.insert(neighbour);
-                }
-            }
-
-        }
-    }
-
-    return vec![];
 }
 
 fn oxygenate(map: &HashMap<Position, LocationType>) -> i32 {
     let (oxygen_position, _) = map.into_iter()
         .find(|(_,&l)| l == OxygenSystem)
         .unwrap();
-    let mut oxygen_map: HashMap<Position, LocationType> = HashMap::new();
-    let mut queue = VecDeque::new();
-    queue.push_back((*oxygen_position, 0));
-    oxygen_map.insert(*oxygen_position, Oxygen);
-    let mut time = 0;
-    while !queue.is_empty() {
-        let (next, d) = queue.pop_front().unwrap();
-        if d > time {
-            time = d;
-        }
-        for n in get_neighbours(&next, map) {
-            if !oxygen_map.contains_key(&n) {
-                oxygen_map.insert(n, Oxygen);
-                queue.push_back((n, d+1));
-            }
-        }
-    }
-    return time;
+    let spread = flood_fill(&[*oxygen_position], |p| map.get(p).is_some_and(|l| *l != Wall));
+    return spread.max_time().unwrap() as i32;
 }
 
 
 
 impl Solver for Day15Solver {
     fn solve(&self, lines: Vec<String>, part_two: bool) -> String {
-        let mut program = intcode_computer::read_program(&lines[0]);
-        let (input_sender, input_receiver) = mpsc::channel();
-        let (output_sender, output_receiver) = mpsc::channel();
-
-        thread::spawn(move || {
-            intcode_computer::run_program(input_receiver, output_sender, &mut program);
-        });
-
-        let mut droid_location = Position{x: 0, y: 0};
-        let mut map = HashMap::new();
-        map.insert(droid_location, Empty);
-        let find_unknown = |map: &HashMap<Position, LocationType>, p: &Position| {
-            return !map.contains_key(p);
+        let program: Vec<i128> = intcode_computer::read_program(&lines[0]);
+        let moves = |p: &Position| {
+            Direction::ALL.iter()
+                .map(|d| (vec![get_command(d)], d.step(p)))
+                .collect()
         };
-        loop {
-            let path = search(droid_location, &map, &find_unknown);
-            if path.is_empty() {
-                break;
-            }
-            for d in path {
-                input_sender.send(d.get_command() as i128).ok();
-                let next_pos = match d {
-                    NORTH => droid_location.up(),
-                    WEST => droid_location.left(),
-                    EAST => droid_location.right(),
-                    SOUTH => droid_location.down(),
-                };
-                match output_receiver.recv() {
-                    Ok(o) => {
-                        let lt = create_location_type(o);
-                        match lt {
-                            Empty => {
-                                map.insert(next_pos, Empty);
-                                droid_location = next_pos;
-                            },
-                            Wall => {map.insert(next_pos, Wall);},
-                            OxygenSystem => {
-                                map.insert(next_pos, OxygenSystem);
-                                droid_location = next_pos;
-                            },
-                            Oxygen => {
-                                panic!("Should not happen")
-                            }
-                        }
-                    }
-                    _ => { panic!("END OF PROGRAM") }
-                }
-            }
-        }
+        let decode = |outputs: &[i128]| match create_location_type(outputs[0]) {
+            Wall => Step::Stop(Wall),
+            lt => Step::Continue(lt),
+        };
+        let is_goal = |_: &Position, lt: &LocationType| !part_two && *lt == OxygenSystem;
+        let start = Position{x: 0, y: 0};
+        let exploration = explore(intcode_vm(&program, &[]), start, Strategy::BreadthFirst, moves, decode, is_goal)
+            .unwrap_or_else(|e| panic!("{}", e));
 
         return if !part_two {
-            let find_oxygen_system = |map: &HashMap<Position, LocationType>, p: &Position| {
-                return map.contains_key(p) && *map.get(p).unwrap() == OxygenSystem;
-            };
-            let path = search(Position { x: 0, y: 0 }, &map, &find_oxygen_system);
-
-            path.len().to_string()
+            let (oxygen_system, _) = exploration.goal.as_ref().expect("No oxygen system found");
+            let path = exploration.path(oxygen_system).unwrap();
+            (path.len() - 1).to_string()
         } else {
+            let mut map = exploration.observations;
+            map.insert(start, Empty);
             let time = oxygenate(&map);
             time.to_string()
         }
@@ -246,7 +79,7 @@ impl Solver for Day15Solver {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lib::test_solver;
+    use crate::test_solver;
 
     #[test]
     fn test_part_one() {
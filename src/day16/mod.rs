@@ -112,7 +112,7 @@ impl Solver for Day16Solver {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lib::test_solver;
+    use crate::test_solver;
 
     fn ftt_pattern_to_vec(position: i32, size: usize) -> Vec<i32> {
         let vec: Vec<i32> = fft_pattern(position).take(size).collect();
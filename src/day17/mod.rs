@@ -1,41 +1,19 @@
-use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::sync::mpsc;
 use std::thread;
 
 use crate::lib::{intcode_computer, Position, Solver};
+use crate::lib::grid::Grid;
+use crate::lib::intcode_computer::Patch;
 
 pub(crate) struct Day17Solver {}
 
 
-fn print_image(image: &HashMap<Position, char>) {
-    let mut min_x = 0;
-    let mut min_y = 0;
-    let mut max_x = 0;
-    let mut max_y = 0;
-    for (p, _) in image.iter() {
-        if p.x < min_x {
-            min_x = p.x;
-        }
-        if p.x > max_x {
-            max_x = p.x;
-        }
-        if p.y < min_y {
-            min_y = p.y;
-        }
-        if p.y > max_y {
-            max_y = p.y;
-        }
+fn print_image(image: &Grid<char>) {
+    if let Some(bounds) = image.bounds() {
+        let output = image.display(|c| *c.unwrap_or(&' '));
+        println!("{} {} {} {}\n\n{}\n", bounds.min.x, bounds.min.y, bounds.max.x, bounds.max.y, output);
     }
-    let mut output = String::new();
-    output.push_str("\n");
-    for y in (min_y..(max_y + 1)).rev() {
-        for x in min_x..(max_x + 1) {
-            let c = image.get(&Position { x, y }).unwrap();
-            output.push(*c);
-        }
-        output.push_str("\n");
-    }
-    println!("{} {} {} {}\n{}", min_x, min_y, max_x, max_y, output.to_string());
 }
 
 impl Solver for Day17Solver {
@@ -48,12 +26,15 @@ impl Solver for Day17Solver {
                 intcode_computer::run_program(input_receiver, output_sender, &mut program);
             });
 
-            let mut image: HashMap<Position, char> = HashMap::new();
+            let mut image: Grid<char> = Grid::sparse();
             let mut position = Position { x: 0, y: 0 };
             loop {
                 match output_receiver.recv() {
                     Ok(o) => {
-                        print!("{}", (o as u8) as char);
+                        match u8::try_from(o) {
+                            Ok(c) => print!("{}", c as char),
+                            Err(_) => println!("{}", o),
+                        }
                         match o {
                             35 => {
                                 image.insert(position, '#');
@@ -96,31 +77,18 @@ impl Solver for Day17Solver {
 
 
             let alignment_parameter_sum: i32 = image.iter()
-                .filter(|&(p, c)| {
-                    if *c != '#' {
-                        return false;
-                    }
-                    let up = &p.up();
-                    let down = &p.down();
-                    let left = &p.left();
-                    let right = &p.right();
-                    image.contains_key(up) && *image.get(up).unwrap() == '#' &&
-                        image.contains_key(down) && *image.get(down).unwrap() == '#' &&
-                        image.contains_key(left) && *image.get(left).unwrap() == '#' &&
-                        image.contains_key(right) && *image.get(right).unwrap() == '#'
-                })
-                .map(|(p, c)| {
-                    return p.x * p.y;
-                }).sum();
+                .filter(|&(p, c)| *c == '#' && image.neighbours(&p).filter(|(_, n)| **n == '#').count() == 4)
+                .map(|(p, _)| p.x * p.y)
+                .sum();
 
             return alignment_parameter_sum.to_string();
         }
 
-        program[0] = 2;
+        let wake_up = Patch::new("wake up robot", 0, 1, 2);
         let (input_sender, input_receiver) = mpsc::channel();
         let (output_sender, output_receiver) = mpsc::channel();
         thread::spawn(move || {
-            intcode_computer::run_program(input_receiver, output_sender, &mut program);
+            intcode_computer::run_patched_program(input_receiver, output_sender, &program, &[wake_up]);
         });
 
         
@@ -151,10 +119,9 @@ impl Solver for Day17Solver {
         loop {
             match output_receiver.recv() {
                 Ok(o) => {
-                    if o < 1_000 {
-                        print!("{}", (o as u8) as char);
-                    } else {
-                        println!("{}", o);
+                    match u8::try_from(o) {
+                        Ok(c) => print!("{}", c as char),
+                        Err(_) => println!("{}", o),
                     }
                 }
                 _ => { break; }
@@ -168,7 +135,7 @@ impl Solver for Day17Solver {
 
 #[cfg(test)]
 mod tests {
-    use crate::lib::test_solver;
+    use crate::test_solver;
 
     use super::*;
 
@@ -0,0 +1,81 @@
+//! Binary search over integers for a predicate that holds up to some value
+//! and fails from there on.
+
+use crate::math::Integer;
+
+/// The first value in `low..high` failing `predicate`, or `high` if every
+/// value passes.
+pub fn partition_point<T: Integer, P: Fn(T) -> bool>(low: T, high: T, predicate: P) -> T {
+    let (mut low, mut high) = (low, high);
+    while low < high {
+        let mid = low + (high - low) / T::two();
+        if predicate(mid) {
+            low = mid + T::ONE;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+/// The largest value from `low` upwards satisfying `predicate`, or `None`
+/// if `low` already fails. The upper bound is found by doubling the step
+/// from `low`, so no limit has to be known in advance.
+pub fn find_max_satisfying<T: Integer, P: Fn(T) -> bool>(low: T, predicate: P) -> Option<T> {
+    if !predicate(low) {
+        return None;
+    }
+    let mut passing = low;
+    let mut step = T::ONE;
+    let failing = loop {
+        if passing > T::MAX - step {
+            if predicate(T::MAX) {
+                return Some(T::MAX);
+            }
+            break T::MAX;
+        }
+        let probe = passing + step;
+        if !predicate(probe) {
+            break probe;
+        }
+        passing = probe;
+        if step <= T::MAX / T::two() {
+            step = step + step;
+        }
+    };
+    Some(partition_point(passing + T::ONE, failing, predicate) - T::ONE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_partition_point() {
+        assert_eq!(partition_point(0, 100, |x| x * x < 50), 8);
+        assert_eq!(partition_point(0, 100, |_| true), 100);
+        assert_eq!(partition_point(0, 100, |_| false), 0);
+        assert_eq!(partition_point(-50i64, 50, |x| x < -7), -7);
+        assert_eq!(partition_point(5u8, 5, |_| true), 5);
+    }
+
+    #[test]
+    fn test_find_max_satisfying() {
+        for limit in [0i64, 1, 2, 3, 7, 8, 1000, 82_892_753].iter() {
+            assert_eq!(find_max_satisfying(0, |x| x <= *limit), Some(*limit));
+        }
+        assert_eq!(find_max_satisfying(10, |x| x < 10), None);
+        assert_eq!(find_max_satisfying(-20i32, |x| x < -3), Some(-4));
+        assert_eq!(find_max_satisfying(0u8, |_| true), Some(u8::MAX));
+        assert_eq!(find_max_satisfying(0u64, |x| x < u64::MAX), Some(u64::MAX - 1));
+
+        let calls = Cell::new(0);
+        let found = find_max_satisfying(1u64, |x| {
+            calls.set(calls.get() + 1);
+            x * x <= 1_000_000_000_000
+        });
+        assert_eq!(found, Some(1_000_000));
+        assert!(calls.get() < 50);
+    }
+}
@@ -0,0 +1,124 @@
+//! Cycle detection for iterated functions. Both detectors keep a constant
+//! number of states, so the period can be far larger than what would fit in
+//! a set of visited states.
+
+/// The iteration from `initial` enters a loop of `length` states after
+/// `start` steps.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The smallest step count with the same state as step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start { n } else { self.start + (n - self.start) % self.length }
+    }
+}
+
+/// The start of the cycle, given two iterations `length` steps apart.
+fn find_start<S: PartialEq, F: Fn(&S) -> S>(initial: S, ahead: S, step: &F) -> usize {
+    let (mut tortoise, mut hare) = (initial, ahead);
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    start
+}
+
+fn advance<S: Clone, F: Fn(&S) -> S>(state: &S, steps: usize, step: &F) -> S {
+    (0..steps).fold(state.clone(), |s, _| step(&s))
+}
+
+/// Floyd's tortoise and hare.
+pub fn floyd<S: Clone + PartialEq, F: Fn(&S) -> S>(initial: S, step: F) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&step(&hare));
+    }
+    let mut length = 1;
+    let mut probe = step(&tortoise);
+    while probe != tortoise {
+        probe = step(&probe);
+        length += 1;
+    }
+    let ahead = advance(&initial, length, &step);
+    Cycle { start: find_start(initial, ahead, &step), length }
+}
+
+/// Brent's algorithm, usually needing fewer steps than `floyd`.
+pub fn brent<S: Clone + PartialEq, F: Fn(&S) -> S>(initial: S, step: F) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+    let ahead = advance(&initial, length, &step);
+    Cycle { start: find_start(initial, ahead, &step), length }
+}
+
+/// The state after `n` steps, skipping whole laps of the cycle.
+pub fn state_at<S: Clone + PartialEq, F: Fn(&S) -> S>(initial: S, step: F, n: usize) -> S {
+    let cycle = brent(initial.clone(), &step);
+    advance(&initial, cycle.reduce(n), &step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn naive<F: Fn(&u64) -> u64>(initial: u64, step: F) -> Cycle {
+        let mut seen = HashMap::new();
+        let mut state = initial;
+        for i in 0.. {
+            if let Some(start) = seen.insert(state, i) {
+                return Cycle { start, length: i - start };
+            }
+            state = step(&state);
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        let rho = |x: &u64| if *x < 5 { x + 1 } else { x - 3 };
+        assert_eq!(floyd(0, rho), Cycle { start: 2, length: 4 });
+        assert_eq!(brent(0, rho), Cycle { start: 2, length: 4 });
+        assert_eq!(brent(7, |x: &u64| *x), Cycle { start: 0, length: 1 });
+
+        for m in [97u64, 1000, 4096, 65_537].iter() {
+            let f = |x: &u64| (x * x + 1) % m;
+            for initial in 0..5 {
+                let expected = naive(initial, f);
+                assert_eq!(floyd(initial, f), expected);
+                assert_eq!(brent(initial, f), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_state_at() {
+        let f = |x: &u64| (x * x + 1) % 1000;
+        let cycle = brent(3, f);
+        assert_eq!(cycle.reduce(1), 1);
+        assert_eq!(cycle.reduce(cycle.start + 5 * cycle.length + 2), cycle.start + 2);
+        for n in [0usize, 1, 10, 123, 4567].iter() {
+            assert_eq!(state_at(3, f, *n), advance(&3, *n, &f));
+        }
+        assert_eq!(state_at(3, f, 1_000_000_000_000), advance(&3, cycle.reduce(1_000_000_000_000), &f));
+    }
+}
@@ -0,0 +1,150 @@
+//! The four grid directions shared by the walkers.
+//!
+//! The y axis points up: `Up` steps to `y + 1`, as `Position::up` does.
+//! Input read row by row, where the row number grows downwards (camera
+//! images, the default `Grid` rendering), uses the `screen_` variants which
+//! flip the sign of y.
+
+use crate::Position;
+
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+use Direction::{Down, Left, Right, Up};
+
+impl Direction {
+    /// All directions, clockwise from `Up`.
+    pub const ALL: [Direction; 4] = [Up, Right, Down, Left];
+
+    pub fn turn_left(&self) -> Direction {
+        match self {
+            Up => Left,
+            Left => Down,
+            Down => Right,
+            Right => Up,
+        }
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        match self {
+            Up => Right,
+            Right => Down,
+            Down => Left,
+            Left => Up,
+        }
+    }
+
+    pub fn reverse(&self) -> Direction {
+        match self {
+            Up => Down,
+            Right => Left,
+            Down => Up,
+            Left => Right,
+        }
+    }
+
+    /// Parses `U`/`D`/`L`/`R` or the compass letters `N`/`S`/`W`/`E`.
+    pub fn from_letter(c: char) -> Option<Direction> {
+        match c {
+            'U' | 'N' => Some(Up),
+            'D' | 'S' => Some(Down),
+            'L' | 'W' => Some(Left),
+            'R' | 'E' => Some(Right),
+            _ => None,
+        }
+    }
+
+    /// Parses one of `^`, `v`, `<` and `>`.
+    pub fn from_arrow(c: char) -> Option<Direction> {
+        match c {
+            '^' => Some(Up),
+            'v' => Some(Down),
+            '<' => Some(Left),
+            '>' => Some(Right),
+            _ => None,
+        }
+    }
+
+    /// Parses either a letter or an arrow.
+    pub fn parse(c: char) -> Option<Direction> {
+        Direction::from_letter(c).or_else(|| Direction::from_arrow(c))
+    }
+
+    pub fn arrow(&self) -> char {
+        match self {
+            Up => '^',
+            Down => 'v',
+            Left => '<',
+            Right => '>',
+        }
+    }
+
+    /// The unit step `(dx, dy)` with y pointing up.
+    pub fn offset(&self) -> (i32, i32) {
+        match self {
+            Up => (0, 1),
+            Down => (0, -1),
+            Left => (-1, 0),
+            Right => (1, 0),
+        }
+    }
+
+    /// The unit step `(dx, dy)` with y pointing down.
+    pub fn screen_offset(&self) -> (i32, i32) {
+        let (dx, dy) = self.offset();
+        (dx, -dy)
+    }
+
+    pub fn step(&self, position: &Position) -> Position {
+        let (x, y) = self.offset();
+        *position + Position { x, y }
+    }
+
+    pub fn screen_step(&self, position: &Position) -> Position {
+        let (x, y) = self.screen_offset();
+        *position + Position { x, y }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turning() {
+        for d in Direction::ALL.iter() {
+            assert_eq!(d.turn_left().turn_right(), *d);
+            assert_eq!(d.turn_right().turn_right(), d.reverse());
+            assert_eq!(d.reverse().reverse(), *d);
+        }
+        assert_eq!(Up.turn_right(), Right);
+        assert_eq!(Up.turn_left(), Left);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("UDLR".chars().map(Direction::parse).collect::<Vec<_>>(), vec![Some(Up), Some(Down), Some(Left), Some(Right)]);
+        assert_eq!("^v<>".chars().map(Direction::parse).collect::<Vec<_>>(), vec![Some(Up), Some(Down), Some(Left), Some(Right)]);
+        assert_eq!(Direction::parse('N'), Some(Up));
+        assert_eq!(Direction::parse('x'), None);
+        for d in Direction::ALL.iter() {
+            assert_eq!(Direction::from_arrow(d.arrow()), Some(*d));
+        }
+    }
+
+    #[test]
+    fn test_step() {
+        let origin = Position { x: 0, y: 0 };
+        assert_eq!(Up.step(&origin), origin.up());
+        assert_eq!(Down.step(&origin), origin.down());
+        assert_eq!(Left.step(&origin), origin.left());
+        assert_eq!(Right.step(&origin), origin.right());
+        assert_eq!(Up.screen_step(&origin), Position { x: 0, y: -1 });
+        assert_eq!(Left.screen_offset(), (-1, 0));
+    }
+}
@@ -0,0 +1,110 @@
+//! Flood fills and connected regions of positions, moving between
+//! orthogonal neighbours.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::Position;
+
+pub struct FloodFill {
+    /// Steps from the nearest source to every reached position.
+    pub distances: HashMap<Position, usize>,
+}
+
+impl FloodFill {
+    /// Steps until the last position is reached, `None` without sources.
+    pub fn max_time(&self) -> Option<usize> {
+        self.distances.values().max().cloned()
+    }
+}
+
+/// Spreads from all `sources` at once into positions accepted by
+/// `passable`. Sources are filled even when not passable themselves.
+pub fn flood_fill<F: Fn(&Position) -> bool>(sources: &[Position], passable: F) -> FloodFill {
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if distances.insert(*source, 0).is_none() {
+            queue.push_back(*source);
+        }
+    }
+    while let Some(p) = queue.pop_front() {
+        let distance = distances[&p] + 1;
+        for n in p.neighbors4().iter() {
+            if !distances.contains_key(n) && passable(n) {
+                distances.insert(*n, distance);
+                queue.push_back(*n);
+            }
+        }
+    }
+    FloodFill { distances }
+}
+
+/// Splits `cells` into its connected regions, largest first.
+pub fn components<I: IntoIterator<Item = Position>>(cells: I) -> Vec<HashSet<Position>> {
+    let cells: Vec<Position> = cells.into_iter().collect();
+    let mut remaining: HashSet<Position> = cells.iter().cloned().collect();
+    let mut regions = Vec::new();
+    for start in cells {
+        if !remaining.contains(&start) {
+            continue;
+        }
+        let region: HashSet<Position> = flood_fill(&[start], |p| remaining.contains(p)).distances.into_keys().collect();
+        for p in &region {
+            remaining.remove(p);
+        }
+        regions.push(region);
+    }
+    regions.sort_by_key(|r| std::cmp::Reverse(r.len()));
+    regions
+}
+
+/// The positions of `region` next to a position outside it.
+pub fn boundary(region: &HashSet<Position>) -> HashSet<Position> {
+    region.iter()
+        .filter(|p| p.neighbors4().iter().any(|n| !region.contains(n)))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    fn open_cells(lines: &[&str]) -> Grid<char> {
+        Grid::parse(lines, |c| if c == '#' { None } else { Some(c) })
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = open_cells(&["#######", "#O....#", "#.###.#", "#.....#", "###O###"]);
+        let sources: Vec<Position> = grid.iter().filter(|(_, c)| **c == 'O').map(|(p, _)| p).collect();
+        let fill = flood_fill(&sources, |p| grid.contains(p));
+        assert_eq!(fill.distances.len(), grid.len());
+        assert_eq!(fill.distances[&Position { x: 3, y: 3 }], 1);
+        assert_eq!(fill.distances[&Position { x: 5, y: 1 }], 4);
+        assert_eq!(fill.max_time(), Some(4));
+
+        let single = flood_fill(&sources[..1], |p| grid.contains(p));
+        assert_eq!(single.max_time(), Some(6));
+        assert_eq!(flood_fill(&[], |_| true).max_time(), None);
+    }
+
+    #[test]
+    fn test_components_and_boundary() {
+        let grid = open_cells(&["..#.#..", "..#.#..", "###.#..", "....#.."]);
+        let regions = components(grid.iter().map(|(p, _)| p));
+        let sizes: Vec<usize> = regions.iter().map(|r| r.len()).collect();
+        assert_eq!(sizes, vec![8, 7, 4]);
+        assert!(regions[2].contains(&Position { x: 0, y: 0 }));
+
+        // A checkerboard, every cell is its own region.
+        let cells = (0..200).flat_map(|x| (0..200).filter(move |y| (x + y) % 2 == 0).map(move |y| Position { x, y }));
+        assert_eq!(components(cells).len(), 20_000);
+
+        let square: HashSet<Position> = (0..3).flat_map(|x| (0..3).map(move |y| Position { x, y })).collect();
+        let edge = boundary(&square);
+        assert_eq!(edge.len(), 8);
+        assert!(!edge.contains(&Position { x: 1, y: 1 }));
+    }
+}
@@ -0,0 +1,125 @@
+//! Exact line of sight between lattice points. An offset is reduced to its
+//! primitive direction by dividing out the gcd of its components, so two
+//! points are in line exactly when their directions are equal.
+//!
+//! Directions use screen coordinates, as parsed by `Grid::parse`: y grows
+//! downwards, so "up" is `(0, -1)` and clockwise turns from up to `(1, 0)`.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use crate::{manhattan_distance, Position};
+use crate::math::gcd;
+
+/// The smallest step along `offset`, `(0, 0)` for the zero offset.
+pub fn primitive(offset: Position) -> Position {
+    let d = gcd(offset.x, offset.y);
+    if d == 0 { offset } else { Position { x: offset.x / d, y: offset.y / d } }
+}
+
+/// Orders directions clockwise starting from up, without computing angles.
+/// Directions are compared by angle only, so `(0, -1)` and `(0, -3)` are equal.
+pub fn cmp_clockwise(a: &Position, b: &Position) -> Ordering {
+    let half = |p: &Position| if p.x > 0 || (p.x == 0 && p.y < 0) { 0 } else { 1 };
+    half(a).cmp(&half(b)).then_with(|| {
+        let cross = i64::from(a.x) * i64::from(b.y) - i64::from(a.y) * i64::from(b.x);
+        0.cmp(&cross)
+    })
+}
+
+/// The other points grouped by their direction from `origin`, directions in
+/// clockwise order and each group sorted from nearest to farthest.
+pub fn group_by_direction(origin: &Position, points: &[Position]) -> Vec<(Position, Vec<Position>)> {
+    let mut groups: HashMap<Position, Vec<Position>> = HashMap::new();
+    for p in points.iter().filter(|p| *p != origin) {
+        groups.entry(primitive(*p - *origin)).or_default().push(*p);
+    }
+    let mut groups: Vec<(Position, Vec<Position>)> = groups.into_iter().collect();
+    for (_, group) in groups.iter_mut() {
+        group.sort_by_key(|p| manhattan_distance(p, origin));
+    }
+    groups.sort_by(|(a, _), (b, _)| cmp_clockwise(a, b));
+    groups
+}
+
+/// How many other points each point can see, a point blocking everything
+/// behind it in the same direction.
+pub fn visible_counts(points: &[Position]) -> HashMap<Position, usize> {
+    points.iter()
+        .map(|origin| {
+            let directions: HashSet<Position> = points.iter()
+                .filter(|p| *p != origin)
+                .map(|p| primitive(*p - *origin))
+                .collect();
+            (*origin, directions.len())
+        })
+        .collect()
+}
+
+/// The order in which a beam sweeping clockwise from up hits the points,
+/// each pass removing only the nearest point in every direction.
+pub fn sweep_order(origin: &Position, points: &[Position]) -> Vec<Position> {
+    let mut groups: Vec<std::vec::IntoIter<Position>> = group_by_direction(origin, points).into_iter()
+        .map(|(_, group)| group.into_iter())
+        .collect();
+    let total = points.iter().filter(|p| *p != origin).count();
+    let mut order = Vec::new();
+    while order.len() < total {
+        order.extend(groups.iter_mut().filter_map(|g| g.next()));
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    fn asteroids(lines: &[&str]) -> Vec<Position> {
+        let grid = Grid::parse(lines, |c| if c == '#' { Some(()) } else { None });
+        let mut points: Vec<Position> = grid.iter().map(|(p, _)| p).collect();
+        points.sort();
+        points
+    }
+
+    #[test]
+    fn test_primitive_and_order() {
+        assert_eq!(primitive(Position { x: 4, y: -6 }), Position { x: 2, y: -3 });
+        assert_eq!(primitive(Position { x: 0, y: -5 }), Position { x: 0, y: -1 });
+        assert_eq!(primitive(Position { x: 0, y: 0 }), Position { x: 0, y: 0 });
+        let mut directions = vec![
+            Position { x: -1, y: -1 }, Position { x: 0, y: 1 }, Position { x: 1, y: 0 },
+            Position { x: -1, y: 0 }, Position { x: 1, y: -1 }, Position { x: 0, y: -1 },
+            Position { x: 1, y: 1 }, Position { x: -1, y: 1 }, Position { x: 3, y: -1 },
+        ];
+        directions.sort_by(cmp_clockwise);
+        let pairs: Vec<(i32, i32)> = directions.iter().map(|p| (p.x, p.y)).collect();
+        assert_eq!(pairs, vec![(0, -1), (1, -1), (3, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)]);
+    }
+
+    #[test]
+    fn test_visible_counts() {
+        let small = asteroids(&[".#..#", ".....", "#####", "....#", "...##"]);
+        let counts = visible_counts(&small);
+        assert_eq!(counts[&Position { x: 3, y: 4 }], 8);
+        assert_eq!(counts.values().max(), Some(&8));
+        assert_eq!(counts[&Position { x: 4, y: 2 }], 5);
+
+        let medium = asteroids(&["......#.#.", "#..#.#....", "..#######.", ".#.#.###..", ".#..#.....",
+            "..#....#.#", "#..#....#.", ".##.#..###", "##...#..#.", ".#....####"]);
+        let counts = visible_counts(&medium);
+        assert_eq!(counts[&Position { x: 5, y: 8 }], 33);
+        assert_eq!(counts.values().max(), Some(&33));
+    }
+
+    #[test]
+    fn test_sweep_order() {
+        let field = asteroids(&[".#....#####...#..", "##...##.#####..##", "##...#...#.#####.", "..#.....X...###..", "..#.#.....#....##"]);
+        let origin = Position { x: 8, y: 3 };
+        let order = sweep_order(&origin, &field);
+        assert_eq!(order.len(), field.len());
+        let first: Vec<(i32, i32)> = order.iter().take(9).map(|p| (p.x, p.y)).collect();
+        assert_eq!(first, vec![(8, 1), (9, 0), (9, 1), (10, 0), (9, 2), (11, 1), (12, 1), (11, 2), (15, 1)]);
+        assert_eq!((order[35].x, order[35].y), (14, 3));
+    }
+}
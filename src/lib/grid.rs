@@ -0,0 +1,330 @@
+//! Two dimensional grids keyed by `Position`. A dense grid stores a
+//! rectangle in a `Vec` and grows when written outside it, a sparse grid is a
+//! `HashMap` for boards that are discovered cell by cell. Rows are numbered
+//! like the lines they were parsed from, so y grows downwards when rendered
+//! unless `y_up` is asked for.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::Position;
+
+/// An inclusive rectangle of positions.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct Bounds {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl Bounds {
+    pub fn new(position: Position) -> Bounds {
+        Bounds { min: position, max: position }
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    pub fn contains(&self, p: &Position) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    pub fn extend(&mut self, p: Position) {
+        self.min = Position { x: self.min.x.min(p.x), y: self.min.y.min(p.y) };
+        self.max = Position { x: self.max.x.max(p.x), y: self.max.y.max(p.y) };
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let (min, max) = (self.min, self.max);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Position { x, y }))
+    }
+
+    fn index(&self, p: &Position) -> usize {
+        (p.y - self.min.y) as usize * self.width() + (p.x - self.min.x) as usize
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+enum Cells<T> {
+    Dense { area: Bounds, cells: Vec<Option<T>> },
+    Sparse(HashMap<Position, T>),
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Grid<T> {
+    cells: Cells<T>,
+    bounds: Option<Bounds>,
+    len: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn sparse() -> Grid<T> {
+        Grid { cells: Cells::Sparse(HashMap::new()), bounds: None, len: 0 }
+    }
+
+    /// An empty dense grid covering `area`.
+    pub fn dense(area: Bounds) -> Grid<T> {
+        let cells = (0..area.width() * area.height()).map(|_| None).collect();
+        Grid { cells: Cells::Dense { area, cells }, bounds: Some(area), len: 0 }
+    }
+
+    /// A dense grid with `width` columns and `height` rows from (0, 0).
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> where T: Clone {
+        let area = Bounds { min: Position { x: 0, y: 0 }, max: Position { x: width as i32 - 1, y: height as i32 - 1 } };
+        let mut grid = Grid::dense(area);
+        area.positions().for_each(|p| { grid.insert(p, value.clone()); });
+        grid
+    }
+
+    /// A dense grid with a row per line, the first line at y = 0. Characters
+    /// mapped to `None` are left empty.
+    pub fn parse<S: AsRef<str>, F: Fn(char) -> Option<T>>(lines: &[S], f: F) -> Grid<T> {
+        let width = lines.iter().map(|l| l.as_ref().chars().count()).max().unwrap_or(0).max(1);
+        let height = lines.len().max(1);
+        let area = Bounds { min: Position { x: 0, y: 0 }, max: Position { x: width as i32 - 1, y: height as i32 - 1 } };
+        let mut grid = Grid::dense(area);
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.as_ref().chars().enumerate() {
+                if let Some(value) = f(c) {
+                    grid.insert(Position { x: x as i32, y: y as i32 }, value);
+                }
+            }
+        }
+        grid
+    }
+
+    /// The smallest rectangle holding every position ever inserted, or the
+    /// whole area of a dense grid.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, p: &Position) -> Option<&T> {
+        match &self.cells {
+            Cells::Dense { area, cells } if area.contains(p) => cells[area.index(p)].as_ref(),
+            Cells::Dense { .. } => None,
+            Cells::Sparse(cells) => cells.get(p),
+        }
+    }
+
+    pub fn get_mut(&mut self, p: &Position) -> Option<&mut T> {
+        match &mut self.cells {
+            Cells::Dense { area, cells } if area.contains(p) => cells[area.index(p)].as_mut(),
+            Cells::Dense { .. } => None,
+            Cells::Sparse(cells) => cells.get_mut(p),
+        }
+    }
+
+    pub fn contains(&self, p: &Position) -> bool {
+        self.get(p).is_some()
+    }
+
+    pub fn insert(&mut self, p: Position, value: T) -> Option<T> {
+        match &mut self.bounds {
+            Some(bounds) => bounds.extend(p),
+            None => self.bounds = Some(Bounds::new(p)),
+        }
+        if let Cells::Dense { area, .. } = &self.cells {
+            if !area.contains(&p) {
+                self.grow(self.bounds.unwrap());
+            }
+        }
+        let old = match &mut self.cells {
+            Cells::Dense { area, cells } => cells[area.index(&p)].replace(value),
+            Cells::Sparse(cells) => cells.insert(p, value),
+        };
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    pub fn remove(&mut self, p: &Position) -> Option<T> {
+        let old = match &mut self.cells {
+            Cells::Dense { area, cells } if area.contains(p) => cells[area.index(p)].take(),
+            Cells::Dense { .. } => None,
+            Cells::Sparse(cells) => cells.remove(p),
+        };
+        if old.is_some() {
+            self.len -= 1;
+        }
+        old
+    }
+
+    fn grow(&mut self, new_area: Bounds) {
+        if let Cells::Dense { area, cells } = &mut self.cells {
+            let mut grown: Vec<Option<T>> = (0..new_area.width() * new_area.height()).map(|_| None).collect();
+            for (p, cell) in area.positions().zip(cells.drain(..)) {
+                grown[new_area.index(&p)] = cell;
+            }
+            *area = new_area;
+            *cells = grown;
+        }
+    }
+
+    /// The occupied cells, row by row for dense grids.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (Position, &T)> + '_> {
+        match &self.cells {
+            Cells::Dense { area, cells } => Box::new(area.positions().zip(cells).filter_map(|(p, c)| c.as_ref().map(|c| (p, c)))),
+            Cells::Sparse(cells) => Box::new(cells.iter().map(|(p, c)| (*p, c))),
+        }
+    }
+
+    fn occupied(&self, positions: Vec<Position>) -> impl Iterator<Item = (Position, &T)> + '_ {
+        positions.into_iter().filter_map(move |n| self.get(&n).map(|c| (n, c)))
+    }
+
+    /// The occupied cells among the four orthogonal neighbours of `p`.
+    pub fn neighbours(&self, p: &Position) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.occupied(p.neighbors4().to_vec())
+    }
+
+    /// The occupied cells among the eight surrounding `p`.
+    pub fn surrounding(&self, p: &Position) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.occupied(p.neighbors8().to_vec())
+    }
+
+    /// The cells of row `y` across the bounds.
+    pub fn row(&self, y: i32) -> impl Iterator<Item = Option<&T>> + '_ {
+        let xs = self.bounds.map(|b| b.min.x..=b.max.x);
+        xs.into_iter().flatten().map(move |x| self.get(&Position { x, y }))
+    }
+
+    /// The cells of column `x` across the bounds.
+    pub fn column(&self, x: i32) -> impl Iterator<Item = Option<&T>> + '_ {
+        let ys = self.bounds.map(|b| b.min.y..=b.max.y);
+        ys.into_iter().flatten().map(move |y| self.get(&Position { x, y }))
+    }
+
+    /// Renders the bounds one line per row, mapping every cell to a char.
+    pub fn display<F: Fn(Option<&T>) -> char>(&self, f: F) -> GridDisplay<'_, T, F> {
+        GridDisplay { grid: self, f, y_up: false }
+    }
+}
+
+impl<T> From<HashMap<Position, T>> for Grid<T> {
+    fn from(cells: HashMap<Position, T>) -> Grid<T> {
+        let mut bounds: Option<Bounds> = None;
+        for p in cells.keys() {
+            match &mut bounds {
+                Some(b) => b.extend(*p),
+                None => bounds = Some(Bounds::new(*p)),
+            }
+        }
+        Grid { len: cells.len(), cells: Cells::Sparse(cells), bounds }
+    }
+}
+
+pub struct GridDisplay<'a, T, F> {
+    grid: &'a Grid<T>,
+    f: F,
+    y_up: bool,
+}
+
+impl<'a, T, F> GridDisplay<'a, T, F> {
+    /// Puts the highest row first, for grids where `Position::up` is up.
+    pub fn y_up(mut self) -> GridDisplay<'a, T, F> {
+        self.y_up = true;
+        self
+    }
+}
+
+impl<'a, T, F: Fn(Option<&T>) -> char> fmt::Display for GridDisplay<'a, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bounds = match self.grid.bounds {
+            Some(bounds) => bounds,
+            None => return Ok(()),
+        };
+        let rows: Vec<i32> = if self.y_up {
+            (bounds.min.y..=bounds.max.y).rev().collect()
+        } else {
+            (bounds.min.y..=bounds.max.y).collect()
+        };
+        for (i, y) in rows.into_iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in self.grid.row(y) {
+                write!(f, "{}", (self.f)(cell))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: i32, y: i32) -> Position {
+        Position { x, y }
+    }
+
+    fn wall(c: char) -> Option<bool> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid = Grid::parse(&["#..", ".#", "..#"], wall);
+        assert_eq!(grid.bounds(), Some(Bounds { min: p(0, 0), max: p(2, 2) }));
+        assert_eq!(grid.len(), 8);
+        assert_eq!(grid.get(&p(1, 1)), Some(&true));
+        assert_eq!(grid.get(&p(2, 1)), None);
+        let render = |c: Option<&bool>| match c {
+            Some(true) => '#',
+            Some(false) => '.',
+            None => ' ',
+        };
+        assert_eq!(grid.display(render).to_string(), "#..\n.# \n..#");
+        assert_eq!(grid.display(render).y_up().to_string(), "..#\n.# \n#..");
+    }
+
+    #[test]
+    fn test_dense_grows() {
+        let mut grid = Grid::filled(2, 2, 0);
+        assert_eq!(grid.insert(p(-1, 3), 7), None);
+        assert_eq!(grid.insert(p(0, 0), 1), Some(0));
+        assert_eq!(grid.bounds(), Some(Bounds { min: p(-1, 0), max: p(1, 3) }));
+        assert_eq!(grid.len(), 5);
+        assert_eq!(grid.get(&p(-1, 3)), Some(&7));
+        assert_eq!(grid.get(&p(1, 1)), Some(&0));
+        assert_eq!(grid.remove(&p(1, 1)), Some(0));
+        assert_eq!(grid.len(), 4);
+        assert_eq!(grid.column(-1).collect::<Vec<Option<&i32>>>(), vec![None, None, None, Some(&7)]);
+    }
+
+    #[test]
+    fn test_sparse() {
+        let mut grid = Grid::sparse();
+        grid.insert(p(5, -5), 'a');
+        grid.insert(p(6, -5), 'b');
+        grid.insert(p(5, -4), 'c');
+        assert_eq!(grid.bounds(), Some(Bounds { min: p(5, -5), max: p(6, -4) }));
+        let mut neighbours: Vec<char> = grid.neighbours(&p(5, -5)).map(|(_, c)| *c).collect();
+        neighbours.sort();
+        assert_eq!(neighbours, vec!['b', 'c']);
+        assert_eq!(grid.surrounding(&p(6, -4)).count(), 3);
+        assert_eq!(grid.row(-5).collect::<Vec<Option<&char>>>(), vec![Some(&'a'), Some(&'b')]);
+        *grid.get_mut(&p(6, -5)).unwrap() = 'B';
+        assert_eq!(grid.display(|c| *c.unwrap_or(&'.')).to_string(), "aB\nc.");
+        assert_eq!(Grid::from(HashMap::from([(p(1, 1), 1)])).bounds(), Some(Bounds::new(p(1, 1))));
+    }
+}
@@ -0,0 +1,229 @@
+//! RGB images rendered from grids, written as binary PPM or PNG. The PNG
+//! encoder stores the pixel data uncompressed, which keeps it free of
+//! dependencies and is plenty for puzzle sized pictures.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::grid::Grid;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+/// Directory that `export` writes to, unset to disable exports.
+pub const IMAGE_DIR_VAR: &str = "AOC_IMAGE_DIR";
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Image {
+        Image { width, height, pixels: vec![background; width * height] }
+    }
+
+    /// Renders the bounds of `grid` with every cell a `scale` by `scale`
+    /// square coloured by `palette`. Row 0 is the top of the image.
+    pub fn from_grid<T, F: Fn(Option<&T>) -> Rgb>(grid: &Grid<T>, palette: F, scale: usize) -> Image {
+        let bounds = match grid.bounds() {
+            Some(bounds) => bounds,
+            None => return Image::new(0, 0, BLACK),
+        };
+        let mut image = Image::new(bounds.width() * scale, bounds.height() * scale, BLACK);
+        for p in bounds.positions() {
+            let colour = palette(grid.get(&p));
+            let (left, top) = ((p.x - bounds.min.x) as usize * scale, (p.y - bounds.min.y) as usize * scale);
+            for y in top..top + scale {
+                for x in left..left + scale {
+                    image.set(x, y, colour);
+                }
+            }
+        }
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    /// Turns the image upside down, for grids whose y axis points up.
+    pub fn flip_vertical(&self) -> Image {
+        let pixels = self.pixels.chunks(self.width.max(1)).rev().flatten().cloned().collect();
+        Image { pixels, ..*self }
+    }
+
+    /// Binary PPM (`P6`).
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.pixels.iter().flatten());
+        bytes
+    }
+
+    /// 8-bit RGB PNG.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend(&(self.width as u32).to_be_bytes());
+        header.extend(&(self.height as u32).to_be_bytes());
+        header.extend(&[8, 2, 0, 0, 0]);
+
+        let mut scanlines = Vec::with_capacity(self.height * (1 + 3 * self.width));
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            scanlines.push(0);
+            scanlines.extend(row.iter().flatten());
+        }
+
+        let mut bytes = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+        write_chunk(&mut bytes, b"IHDR", &header);
+        write_chunk(&mut bytes, b"IDAT", &zlib_stored(&scanlines));
+        write_chunk(&mut bytes, b"IEND", &[]);
+        bytes
+    }
+
+    /// Writes the image as PNG or PPM depending on the extension of `path`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => self.to_png(),
+            Some("ppm") => self.to_ppm(),
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Unknown image format {}", path.display()))),
+        };
+        fs::write(path, bytes)
+    }
+}
+
+/// Saves `image` as `<name>.png` in the directory named by `AOC_IMAGE_DIR`
+/// and returns the path written, or does nothing when it is unset.
+pub fn export(name: &str, image: &Image) -> io::Result<Option<PathBuf>> {
+    let dir = match env::var_os(IMAGE_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => return Ok(None),
+    };
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.png", name));
+    image.save(&path)?;
+    Ok(Some(path))
+}
+
+fn write_chunk(bytes: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    bytes.extend(&(data.len() as u32).to_be_bytes());
+    let start = bytes.len();
+    bytes.extend(kind);
+    bytes.extend(data);
+    let crc = crc32(&bytes[start..]);
+    bytes.extend(&crc.to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+/// A zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = if data.is_empty() { vec![&[]] } else { data.chunks(0xffff).collect() };
+    for (i, block) in blocks.iter().enumerate() {
+        bytes.push(if i + 1 == blocks.len() { 1 } else { 0 });
+        let len = block.len() as u16;
+        bytes.extend(&len.to_le_bytes());
+        bytes.extend(&(!len).to_le_bytes());
+        bytes.extend(*block);
+    }
+    bytes.extend(&adler32(data).to_be_bytes());
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Position;
+
+    fn pixel_of(image: &Image, p: Position, scale: usize) -> Rgb {
+        image.get(p.x as usize * scale, p.y as usize * scale)
+    }
+
+    fn checker() -> Grid<bool> {
+        Grid::parse(&["#.", ".#", "##"], |c| Some(c == '#'))
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let palette = |c: Option<&bool>| if c == Some(&true) { WHITE } else { BLACK };
+        let image = Image::from_grid(&checker(), palette, 3);
+        assert_eq!((image.width(), image.height()), (6, 9));
+        assert_eq!(pixel_of(&image, Position { x: 0, y: 0 }, 3), WHITE);
+        assert_eq!(pixel_of(&image, Position { x: 1, y: 0 }, 3), BLACK);
+        assert_eq!(image.get(5, 5), WHITE);
+        assert_eq!(image.get(3, 2), BLACK);
+        let flipped = image.flip_vertical();
+        assert_eq!(flipped.get(3, 0), WHITE);
+        assert_eq!(flipped.flip_vertical(), image);
+        assert_eq!(Image::from_grid(&Grid::<bool>::sparse(), palette, 3).width(), 0);
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut image = Image::new(2, 1, BLACK);
+        image.set(1, 0, [1, 2, 3]);
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\x00\x00\x00\x01\x02\x03".to_vec());
+    }
+
+    #[test]
+    fn test_png() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+
+        let mut image = Image::new(2, 2, WHITE);
+        image.set(0, 1, [10, 20, 30]);
+        let png = image.to_png();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..29], &[0, 0, 0, 2, 0, 0, 0, 2, 8, 2, 0, 0, 0]);
+        assert_eq!(&png[png.len() - 12..], b"\x00\x00\x00\x00IEND\xae\x42\x60\x82");
+
+        // IDAT holds one stored block with a filter byte before each row.
+        let idat = &png[33..png.len() - 12];
+        assert_eq!(&idat[4..8], b"IDAT");
+        let stream = &idat[8..idat.len() - 4];
+        assert_eq!(&stream[..5], &[0x78, 0x01, 1, 14, 0]);
+        let raw = &stream[7..stream.len() - 4];
+        assert_eq!(raw, &[0, 255, 255, 255, 255, 255, 255, 0, 10, 20, 30, 255, 255, 255]);
+
+        let big = Image::new(200, 200, BLACK).to_png();
+        assert!(big.len() > 200 * 200 * 3);
+    }
+}
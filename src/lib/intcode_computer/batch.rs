@@ -0,0 +1,178 @@
+//! Runs one program against many variants on a pool of worker threads.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use super::{Event, IntcodeError, IntcodeVm, Patch, Word};
+use super::patch::PatchError;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Variant<W> {
+    pub patches: Vec<Patch<W>>,
+    pub inputs: Vec<W>,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct BatchRun<W: Word> {
+    pub outputs: Vec<W>,
+    pub memory: HashMap<W, W>,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum BatchError<W> {
+    Patch(PatchError<W>),
+    Intcode(IntcodeError<W>),
+}
+
+impl<W: fmt::Display> fmt::Display for BatchError<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchError::Patch(e) => write!(f, "{}", e),
+            BatchError::Intcode(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+/// Maps `f` over `items` on `workers` threads. Results are in item order.
+pub fn parallel_map<T, R, F>(items: &[T], workers: usize, f: F) -> Vec<R>
+    where T: Sync, R: Send, F: Fn(&T) -> R + Sync {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..workers.max(1) {
+            let sender = sender.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                if i >= items.len() {
+                    break;
+                }
+                sender.send((i, f(&items[i]))).ok();
+            });
+        }
+    });
+    drop(sender);
+    let mut results: Vec<(usize, R)> = receiver.iter().collect();
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
+
+/// Returns the lowest index for which `f` gives a result, together with that
+/// result. Workers stop picking up items past the best match found so far.
+pub fn parallel_find<T, R, F>(items: &[T], workers: usize, f: F) -> Option<(usize, R)>
+    where T: Sync, R: Send, F: Fn(&T) -> Option<R> + Sync {
+    parallel_find_index(items.len(), workers, |i| f(&items[i]))
+}
+
+/// Like `parallel_find` over the indices `0..count`, handed out one at a
+/// time so they are never collected.
+pub fn parallel_find_index<R, F>(count: usize, workers: usize, f: F) -> Option<(usize, R)>
+    where R: Send, F: Fn(usize) -> Option<R> + Sync {
+    let next = AtomicUsize::new(0);
+    let best = AtomicUsize::new(usize::MAX);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..workers.max(1) {
+            let sender = sender.clone();
+            let (next, best, f) = (&next, &best, &f);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                if i >= count || i > best.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Some(r) = f(i) {
+                    best.fetch_min(i, Ordering::SeqCst);
+                    sender.send((i, r)).ok();
+                }
+            });
+        }
+    });
+    drop(sender);
+    receiver.iter().min_by_key(|(i, _)| *i)
+}
+
+/// Runs the patched program with the variant's inputs until it halts.
+pub fn run_variant<W: Word>(program: &[W], variant: &Variant<W>) -> Result<BatchRun<W>, BatchError<W>> {
+    let mut vm = IntcodeVm::patched(program, &variant.patches).map_err(BatchError::Patch)?;
+    variant.inputs.iter().for_each(|i| vm.push_input(i.clone()));
+    let mut outputs = Vec::new();
+    loop {
+        match vm.run_until_event().map_err(BatchError::Intcode)? {
+            Event::Output(o) => outputs.push(o),
+            Event::NeedInput => return Err(BatchError::Intcode(IntcodeError::InputClosed { ip: vm.ip().clone() })),
+            Event::Halted => return Ok(BatchRun { outputs, memory: vm.into_memory() }),
+        }
+    }
+}
+
+pub fn run_batch<W: Word>(program: &[W], variants: &[Variant<W>], workers: usize) -> Vec<Result<BatchRun<W>, BatchError<W>>> {
+    parallel_map(variants, workers, |v| run_variant(program, v))
+}
+
+/// The first variant, in order, whose successful run satisfies `predicate`.
+pub fn find_first_run<W, P>(program: &[W], variants: &[Variant<W>], workers: usize, predicate: P) -> Option<(usize, BatchRun<W>)>
+    where W: Word, P: Fn(&BatchRun<W>) -> bool + Sync {
+    parallel_find(variants, workers, |v| run_variant(program, v).ok().filter(|run| predicate(run)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_map_keeps_order() {
+        let items: Vec<u64> = (0..200).collect();
+        assert_eq!(parallel_map(&items, 4, |i| i * i), items.iter().map(|i| i * i).collect::<Vec<u64>>());
+        assert_eq!(parallel_map(&items, 0, |i| i + 1).len(), 200);
+    }
+
+    #[test]
+    fn test_parallel_find_returns_lowest_match() {
+        let items: Vec<u64> = (0..1000).collect();
+        assert_eq!(parallel_find(&items, 8, |i| if i % 97 == 96 { Some(i * 2) } else { None }), Some((96, 192)));
+        assert_eq!(parallel_find(&items, 8, |_| None::<u64>), None);
+    }
+
+    #[test]
+    fn test_run_batch() {
+        // Outputs input + [1], where [1] is the patched addend.
+        let program: Vec<i128> = vec![3, 9, 1, 9, 10, 9, 4, 9, 99, 0, 0];
+        let variants: Vec<Variant<i128>> = (0..10)
+            .map(|i| Variant { patches: vec![Patch::new("addend", 10, 0, i)], inputs: vec![100] })
+            .collect();
+        let runs = run_batch(&program, &variants, 3);
+        let outputs: Vec<Vec<i128>> = runs.into_iter().map(|r| r.unwrap().outputs).collect();
+        assert_eq!(outputs, (100..110).map(|o| vec![o]).collect::<Vec<Vec<i128>>>());
+
+        let found = find_first_run(&program, &variants, 3, |run| run.outputs == vec![107]);
+        assert_eq!(found.map(|(i, run)| (i, run.memory[&9])), Some((7, 107)));
+    }
+
+    #[test]
+    fn test_sweep_includes_range_ends() {
+        // [0] = 100 * noun + verb, with noun at 1 and verb at 6.
+        let program: Vec<i128> = vec![1102, 0, 100, 11, 1001, 11, 0, 0, 99, 0, 0, 0];
+        let variants: Vec<Variant<i128>> = (0..=99)
+            .flat_map(|noun| (0..=99).map(move |verb| (noun, verb)))
+            .map(|(noun, verb)| Variant { patches: vec![Patch::new("noun", 1, 0, noun), Patch::new("verb", 6, 0, verb)], inputs: vec![] })
+            .collect();
+        let found = find_first_run(&program, &variants, 4, |run| run.memory[&0] == 9999);
+        assert_eq!(found.map(|(i, _)| i), Some(variants.len() - 1));
+    }
+
+    #[test]
+    fn test_run_variant_errors() {
+        let program: Vec<i128> = vec![3, 0, 99];
+        let no_input = Variant { patches: vec![], inputs: vec![] };
+        assert_eq!(run_variant(&program, &no_input), Err(BatchError::Intcode(IntcodeError::InputClosed { ip: 0 })));
+        let bad_patch = Variant { patches: vec![Patch::new("x", 1, 5, 1)], inputs: vec![1] };
+        assert!(matches!(run_variant(&program, &bad_patch), Err(BatchError::Patch(_))));
+    }
+}
@@ -0,0 +1,315 @@
+//! Ahead-of-time compilation of Intcode programs into pre-bound closures.
+//!
+//! Every address of the program image that decodes as an instruction gets a
+//! closure with its modes and parameters already resolved, so running it
+//! skips decoding. When the program overwrites one of its compiled
+//! instructions, that instruction is dropped and the interpreter executes it
+//! from then on.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::mpsc::{Sender, Receiver};
+
+use super::{decode_modes, Event, IntcodeError, IntcodeVm, Mode, Word};
+
+type Op<W> = Box<dyn Fn(&mut IntcodeVm<W>) -> Result<Option<Event<W>>, IntcodeError<W>> + Send + Sync>;
+
+#[derive(Clone, Debug)]
+enum Operand<W> {
+    Value(W),
+    Address(W),
+    Relative(W),
+}
+
+impl<W: Word> Operand<W> {
+    fn address(&self, vm: &IntcodeVm<W>) -> Result<W, IntcodeError<W>> {
+        match self {
+            Operand::Value(_) => panic!("Immediate operand has no address"),
+            Operand::Address(address) => Ok(address.clone()),
+            Operand::Relative(offset) => {
+                let address = offset.checked_add(&vm.relative_base).ok_or_else(|| vm.overflow())?;
+                if address < W::zero() {
+                    return Err(IntcodeError::NegativeAddress { ip: vm.ip.clone(), address });
+                }
+                Ok(address)
+            }
+        }
+    }
+
+    fn read(&self, vm: &IntcodeVm<W>) -> Result<W, IntcodeError<W>> {
+        match self {
+            Operand::Value(value) => Ok(value.clone()),
+            _ => Ok(vm.read(&self.address(vm)?)),
+        }
+    }
+}
+
+fn parameter_count(op: i64) -> Option<usize> {
+    match op {
+        1 | 2 | 7 | 8 => Some(3),
+        5 | 6 => Some(2),
+        3 | 4 | 9 => Some(1),
+        99 => Some(0),
+        _ => None,
+    }
+}
+
+fn compile_at<W: Word>(program: &[W], at: usize) -> Option<(usize, Op<W>)> {
+    let raw = program[at].to_i64().filter(|i| *i >= 0)?;
+    let modes = decode_modes(raw)?;
+    let op = raw % 100;
+    let length = parameter_count(op)? + 1;
+    let parameters = program.get(at + 1..at + length)?;
+    let read = |n: usize| match modes[n] {
+        Mode::Position if parameters[n] < W::zero() => None,
+        Mode::Position => Some(Operand::Address(parameters[n].clone())),
+        Mode::Immediate => Some(Operand::Value(parameters[n].clone())),
+        Mode::Relative => Some(Operand::Relative(parameters[n].clone())),
+    };
+    let write = |n: usize| match modes[n] {
+        Mode::Immediate => None,
+        _ => read(n),
+    };
+    let next = W::from_i64((at + length) as i64);
+
+    let compiled: Op<W> = match op {
+        1 | 2 => {
+            let (a, b, c) = (read(0)?, read(1)?, write(2)?);
+            Box::new(move |vm| {
+                let (x, y) = (a.read(vm)?, b.read(vm)?);
+                let res = if op == 1 { x.checked_add(&y) } else { x.checked_mul(&y) };
+                let res = res.ok_or_else(|| vm.overflow())?;
+                let address = c.address(vm)?;
+                vm.write(address, res);
+                vm.ip = next.clone();
+                Ok(None)
+            })
+        }
+        3 => {
+            let a = write(0)?;
+            Box::new(move |vm| {
+                if vm.inputs.is_empty() {
+                    return Ok(Some(vm.pause(Event::NeedInput)?));
+                }
+                let address = a.address(vm)?;
+                let value = vm.take_input()?;
+                vm.write(address, value);
+                vm.ip = next.clone();
+                Ok(None)
+            })
+        }
+        4 => {
+            let a = read(0)?;
+            Box::new(move |vm| {
+                let value = a.read(vm)?;
+                vm.ip = next.clone();
+                Ok(Some(vm.output(value)?))
+            })
+        }
+        5 | 6 => {
+            let (a, b) = (read(0)?, read(1)?);
+            Box::new(move |vm| {
+                let (x, target) = (a.read(vm)?, b.read(vm)?);
+                vm.ip = if (x != W::zero()) == (op == 5) { target } else { next.clone() };
+                Ok(None)
+            })
+        }
+        7 | 8 => {
+            let (a, b, c) = (read(0)?, read(1)?, write(2)?);
+            Box::new(move |vm| {
+                let (x, y) = (a.read(vm)?, b.read(vm)?);
+                let check = if op == 7 { x < y } else { x == y };
+                let address = c.address(vm)?;
+                vm.write(address, if check { W::one() } else { W::zero() });
+                vm.ip = next.clone();
+                Ok(None)
+            })
+        }
+        9 => {
+            let a = read(0)?;
+            Box::new(move |vm| {
+                let x = a.read(vm)?;
+                vm.relative_base = vm.relative_base.checked_add(&x).ok_or_else(|| vm.overflow())?;
+                vm.ip = next.clone();
+                Ok(None)
+            })
+        }
+        _ => Box::new(|vm| {
+            vm.halted = true;
+            Ok(Some(vm.pause(Event::Halted)?))
+        }),
+    };
+    Some((length, compiled))
+}
+
+pub struct CompiledProgram<W: Word> {
+    program: Vec<W>,
+    ops: Vec<Option<Op<W>>>,
+    covering: Vec<Vec<usize>>,
+}
+
+pub fn compile<W: Word>(program: &[W]) -> CompiledProgram<W> {
+    let mut ops = Vec::with_capacity(program.len());
+    let mut covering = vec![Vec::new(); program.len()];
+    for at in 0..program.len() {
+        match compile_at(program, at) {
+            Some((length, op)) => {
+                (at..at + length).for_each(|a| covering[a].push(at));
+                ops.push(Some(op));
+            }
+            None => ops.push(None),
+        }
+    }
+    CompiledProgram { program: program.to_vec(), ops, covering }
+}
+
+impl<W: Word> CompiledProgram<W> {
+    pub fn compiled_instructions(&self) -> usize {
+        self.ops.iter().filter(|op| op.is_some()).count()
+    }
+
+    pub fn start(&self) -> CompiledVm<'_, W> {
+        CompiledVm { compiled: self, vm: IntcodeVm::new(&self.program), overwritten: vec![false; self.ops.len()] }
+    }
+}
+
+pub struct CompiledVm<'a, W: Word> {
+    compiled: &'a CompiledProgram<W>,
+    vm: IntcodeVm<W>,
+    overwritten: Vec<bool>,
+}
+
+impl<'a, W: Word> CompiledVm<'a, W> {
+    pub fn push_input(&mut self, value: W) {
+        self.vm.push_input(value);
+    }
+
+    pub fn into_memory(self) -> HashMap<W, W> {
+        self.vm.into_memory()
+    }
+
+    pub fn run_until_event(&mut self) -> Result<Event<W>, IntcodeError<W>> {
+        loop {
+            if let Some(event) = self.step()? {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Executes one instruction, compiled if it still can be.
+    pub fn step(&mut self) -> Result<Option<Event<W>>, IntcodeError<W>> {
+        let event = match self.current_op() {
+            Some(op) => op(&mut self.vm)?,
+            None => self.vm.step()?,
+        };
+        if let Some(address) = self.vm.last_write.take() {
+            self.invalidate(&address);
+        }
+        Ok(event)
+    }
+
+    fn current_op(&self) -> Option<&'a Op<W>> {
+        if self.vm.halted {
+            return None;
+        }
+        let at = self.index(&self.vm.ip)?;
+        if self.overwritten[at] {
+            return None;
+        }
+        self.compiled.ops[at].as_ref()
+    }
+
+    fn index(&self, address: &W) -> Option<usize> {
+        address.to_i64()
+            .and_then(|a| usize::try_from(a).ok())
+            .filter(|a| *a < self.overwritten.len())
+    }
+
+    fn invalidate(&mut self, address: &W) {
+        if let Some(a) = self.index(address) {
+            for &start in &self.compiled.covering[a] {
+                self.overwritten[start] = true;
+            }
+        }
+    }
+}
+
+/// Drop-in alternative to `try_run_program` that runs compiled closures.
+pub fn try_run_compiled_program<W: Word>(input: Receiver<W>, output: Sender<W>, program: &[W]) -> Result<(W, HashMap<W, W>), IntcodeError<W>> {
+    let compiled = compile(program);
+    let mut vm = compiled.start();
+    let mut io = W::zero();
+    loop {
+        match vm.run_until_event()? {
+            Event::Output(value) => {
+                io = value.clone();
+                output.send(value).ok();
+            }
+            Event::NeedInput => match input.recv() {
+                Ok(value) => vm.push_input(value),
+                Err(_) => return Err(IntcodeError::InputClosed { ip: vm.vm.ip().clone() }),
+            },
+            Event::Halted => return Ok((io, vm.into_memory())),
+        }
+    }
+}
+
+/// Drop-in alternative to `run_program` that runs compiled closures.
+pub fn run_compiled_program(input: Receiver<i128>, output: Sender<i128>, program: &[i128]) -> (i128, HashMap<i128, i128>) {
+    try_run_compiled_program(input, output, program).unwrap_or_else(|e| panic!("{}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::intcode_computer::{read_program, run_program};
+
+    type Runner = fn(Receiver<i128>, Sender<i128>, &[i128]) -> (i128, HashMap<i128, i128>);
+
+    fn outputs<W: Word>(vm: &mut CompiledVm<W>) -> Vec<W> {
+        let mut outputs = Vec::new();
+        while let Ok(Event::Output(o)) = vm.run_until_event() {
+            outputs.push(o);
+        }
+        outputs
+    }
+
+    #[test]
+    fn test_compiles_every_decodable_address() {
+        let compiled = compile::<i64>(&read_program("1101,2,7,5,104,0,99"));
+        // Everything but the 0 at 5 decodes, e.g. 2 at 1 as multiply 7,5,104.
+        assert_eq!(compiled.compiled_instructions(), 6);
+    }
+
+    #[test]
+    fn test_overwritten_parameter_falls_back() {
+        let compiled = compile::<i64>(&read_program("1101,2,7,5,104,0,99"));
+        assert_eq!(outputs(&mut compiled.start()), vec![9]);
+    }
+
+    #[test]
+    fn test_overwritten_opcode_falls_back() {
+        let compiled = compile::<i64>(&read_program("1101,100,-1,4,1,0,0,0,99"));
+        let mut vm = compiled.start();
+        assert_eq!(vm.run_until_event(), Ok(Event::Halted));
+        assert_eq!(vm.into_memory()[&0], 1101);
+    }
+
+    #[test]
+    fn test_matches_interpreter() {
+        let program: Vec<i128> = read_program("3,12,1001,12,-1,12,4,12,1005,12,2,99,0");
+        let run = |runner: Runner| {
+            let (input_sender, input_receiver) = std::sync::mpsc::channel();
+            let (output_sender, output_receiver) = std::sync::mpsc::channel();
+            input_sender.send(50_000).ok();
+            let (_, memory) = runner(input_receiver, output_sender, &program);
+            (output_receiver.iter().count(), memory)
+        };
+        let (interpreted_outputs, interpreted_memory) = run(run_program);
+        let (compiled_outputs, compiled_memory) = run(run_compiled_program);
+        assert_eq!(interpreted_outputs, 50_000);
+        assert_eq!(compiled_outputs, interpreted_outputs);
+        assert_eq!(compiled_memory, interpreted_memory);
+    }
+}
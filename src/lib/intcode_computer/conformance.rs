@@ -0,0 +1,144 @@
+//! Declarative conformance cases for the Intcode machine, taken from the
+//! 2019 puzzle descriptions plus hand written cases for the mode and error
+//! combinations the puzzles don't spell out.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Sender, Receiver};
+
+use num_bigint::BigInt;
+
+use super::{try_run_program, read_program, IntcodeError, Word};
+use super::compiler::try_run_compiled_program;
+
+type Runner<W> = fn(Receiver<W>, Sender<W>, &[W]) -> Result<(W, HashMap<W, W>), IntcodeError<W>>;
+
+struct Case {
+    name: &'static str,
+    program: &'static str,
+    inputs: &'static [i64],
+    outputs: &'static [i64],
+    memory: &'static [(i64, i64)],
+    error: Option<IntcodeError<i64>>,
+}
+
+const fn case(name: &'static str, program: &'static str) -> Case {
+    Case { name, program, inputs: &[], outputs: &[], memory: &[], error: None }
+}
+
+const DAY_5_LARGER_EXAMPLE: &str = "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99";
+
+const CASES: &[Case] = &[
+    // Day 2: add and multiply in position mode.
+    Case { memory: &[(0, 3500), (3, 70)], ..case("day 2 example", "1,9,10,3,2,3,11,0,99,30,40,50") },
+    Case { memory: &[(0, 2)], ..case("day 2 add", "1,0,0,0,99") },
+    Case { memory: &[(3, 6)], ..case("day 2 multiply", "2,3,0,3,99") },
+    Case { memory: &[(5, 9801)], ..case("day 2 multiply past program", "2,4,4,5,99,0") },
+    Case { memory: &[(0, 30), (4, 2)], ..case("day 2 self modifying", "1,1,1,4,99,5,6,0,99") },
+    // Day 5: input, output, immediate mode, jumps and comparisons.
+    Case { inputs: &[42], outputs: &[42], memory: &[(0, 42)], ..case("day 5 echo", "3,0,4,0,99") },
+    Case { memory: &[(4, 99)], ..case("day 5 immediate multiply", "1002,4,3,4,33") },
+    Case { memory: &[(4, 99)], ..case("day 5 negative immediate", "1101,100,-1,4,0") },
+    Case { outputs: &[7], ..case("day 5 immediate output", "104,7,99") },
+    Case { inputs: &[8], outputs: &[1], ..case("day 5 equals position", "3,9,8,9,10,9,4,9,99,-1,8") },
+    Case { inputs: &[7], outputs: &[0], ..case("day 5 not equals position", "3,9,8,9,10,9,4,9,99,-1,8") },
+    Case { inputs: &[5], outputs: &[1], ..case("day 5 less than position", "3,9,7,9,10,9,4,9,99,-1,8") },
+    Case { inputs: &[9], outputs: &[0], ..case("day 5 not less than position", "3,9,7,9,10,9,4,9,99,-1,8") },
+    Case { inputs: &[8], outputs: &[1], ..case("day 5 equals immediate", "3,3,1108,-1,8,3,4,3,99") },
+    Case { inputs: &[7], outputs: &[0], ..case("day 5 not equals immediate", "3,3,1108,-1,8,3,4,3,99") },
+    Case { inputs: &[7], outputs: &[1], ..case("day 5 less than immediate", "3,3,1107,-1,8,3,4,3,99") },
+    Case { inputs: &[9], outputs: &[0], ..case("day 5 not less than immediate", "3,3,1107,-1,8,3,4,3,99") },
+    Case { inputs: &[0], outputs: &[0], ..case("day 5 jump if false position", "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9") },
+    Case { inputs: &[5], outputs: &[1], ..case("day 5 no jump if false position", "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9") },
+    Case { inputs: &[0], outputs: &[0], ..case("day 5 no jump if true immediate", "3,3,1105,-1,9,1101,0,0,12,4,12,99,1") },
+    Case { inputs: &[5], outputs: &[1], ..case("day 5 jump if true immediate", "3,3,1105,-1,9,1101,0,0,12,4,12,99,1") },
+    Case { inputs: &[7], outputs: &[999], ..case("day 5 larger example below", DAY_5_LARGER_EXAMPLE) },
+    Case { inputs: &[8], outputs: &[1000], ..case("day 5 larger example equal", DAY_5_LARGER_EXAMPLE) },
+    Case { inputs: &[9], outputs: &[1001], ..case("day 5 larger example above", DAY_5_LARGER_EXAMPLE) },
+    // Day 9: relative mode and large numbers.
+    Case {
+        outputs: &[109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99],
+        memory: &[(100, 16), (101, 1)],
+        ..case("day 9 quine", "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99")
+    },
+    Case { outputs: &[1_219_070_632_396_864], ..case("day 9 large multiply", "1102,34915192,34915192,7,4,7,99,0") },
+    Case { outputs: &[1_125_899_906_842_624], ..case("day 9 large output", "104,1125899906842624,99") },
+    // Relative mode for every parameter kind.
+    Case { inputs: &[7], outputs: &[7], memory: &[(10, 7)], ..case("relative input", "109,10,203,0,204,0,99") },
+    Case { outputs: &[5], memory: &[(20, 5)], ..case("relative write", "109,20,21101,2,3,0,204,0,99") },
+    Case { outputs: &[4404], memory: &[(20, 4404)], ..case("relative reads", "109,2,2202,-1,0,20,4,20,99") },
+    Case { outputs: &[99], ..case("relative base adjusted twice", "109,20,109,-15,204,1,99") },
+    Case { outputs: &[1], ..case("relative jump if true", "109,1,1205,0,7,104,0,104,1,99") },
+    Case { outputs: &[0, 1], ..case("relative jump if false", "109,1,1206,2,7,104,0,104,1,99") },
+    Case { outputs: &[1], memory: &[(20, 1)], ..case("relative equals", "109,20,22108,99,-12,0,204,0,99") },
+    Case { outputs: &[1], memory: &[(21, 1)], ..case("relative less than", "109,20,22107,98,-12,1,204,1,99") },
+    // Errors.
+    Case { error: Some(IntcodeError::InvalidOpcode { ip: 0, instruction: 42 }), ..case("invalid op code", "42") },
+    Case { error: Some(IntcodeError::InvalidOpcode { ip: 4, instruction: -1 }), ..case("negative op code", "1,0,0,0,-1") },
+    Case { error: Some(IntcodeError::InvalidMode { ip: 0, instruction: 304 }), ..case("invalid mode", "304,0,99") },
+    Case { error: Some(IntcodeError::InvalidMode { ip: 0, instruction: 11101 }), ..case("immediate write", "11101,1,1,3,99") },
+    Case { inputs: &[1], error: Some(IntcodeError::InvalidMode { ip: 0, instruction: 103 }), ..case("immediate input", "103,0,99") },
+    Case { error: Some(IntcodeError::NegativeAddress { ip: 0, address: -1 }), ..case("negative address", "4,-1,99") },
+    Case { error: Some(IntcodeError::NegativeAddress { ip: 2, address: -1 }), ..case("negative relative address", "109,-3,204,2,99") },
+    Case { inputs: &[1], error: Some(IntcodeError::InputClosed { ip: 2 }), ..case("input exhausted", "3,0,3,1,99") },
+];
+
+fn to_i64<W: Word>(w: &W) -> i64 {
+    w.to_i64().expect("Value does not fit i64")
+}
+
+fn map_error<W: Word>(error: IntcodeError<W>) -> IntcodeError<i64> {
+    match error {
+        IntcodeError::Overflow { ip } => IntcodeError::Overflow { ip: to_i64(&ip) },
+        IntcodeError::InvalidOpcode { ip, instruction } => IntcodeError::InvalidOpcode { ip: to_i64(&ip), instruction: to_i64(&instruction) },
+        IntcodeError::InvalidMode { ip, instruction } => IntcodeError::InvalidMode { ip: to_i64(&ip), instruction: to_i64(&instruction) },
+        IntcodeError::NegativeAddress { ip, address } => IntcodeError::NegativeAddress { ip: to_i64(&ip), address: to_i64(&address) },
+        IntcodeError::InputClosed { ip } => IntcodeError::InputClosed { ip: to_i64(&ip) },
+        IntcodeError::Log { ip, kind } => IntcodeError::Log { ip: to_i64(&ip), kind },
+    }
+}
+
+fn run_case<W: Word>(case: &Case, runner: Runner<W>) {
+    let program: Vec<W> = read_program(case.program);
+    let (input_sender, input_receiver) = mpsc::channel();
+    let (output_sender, output_receiver) = mpsc::channel();
+    for &i in case.inputs {
+        input_sender.send(W::from_i64(i)).ok();
+    }
+    drop(input_sender);
+
+    let result = runner(input_receiver, output_sender, &program);
+    let outputs: Vec<i64> = output_receiver.iter().map(|o| to_i64(&o)).collect();
+    assert_eq!(outputs, case.outputs, "outputs of '{}'", case.name);
+
+    match result {
+        Ok((_, memory)) => {
+            assert_eq!(None, case.error, "error of '{}'", case.name);
+            for (address, expected) in case.memory {
+                let value = memory.get(&W::from_i64(*address)).map(to_i64).unwrap_or(0);
+                assert_eq!(value, *expected, "memory[{}] of '{}'", address, case.name);
+            }
+        }
+        Err(e) => assert_eq!(Some(map_error(e)), case.error, "error of '{}'", case.name),
+    }
+}
+
+#[test]
+fn test_conformance_i64() {
+    CASES.iter().for_each(|c| run_case::<i64>(c, try_run_program));
+}
+
+#[test]
+fn test_conformance_i128() {
+    CASES.iter().for_each(|c| run_case::<i128>(c, try_run_program));
+}
+
+#[test]
+fn test_conformance_big_int() {
+    CASES.iter().for_each(|c| run_case::<BigInt>(c, try_run_program));
+}
+
+#[test]
+fn test_conformance_compiled() {
+    CASES.iter().for_each(|c| run_case::<i64>(c, try_run_compiled_program));
+    CASES.iter().for_each(|c| run_case::<BigInt>(c, try_run_compiled_program));
+}
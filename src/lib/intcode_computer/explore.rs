@@ -0,0 +1,226 @@
+//! Exploration of the states reachable through an interactive Intcode
+//! program. Instead of steering one machine back and forth, the machine is
+//! cloned at every decision point and each clone tries one move.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use super::{Event, IntcodeError, IntcodeVm, Word};
+use super::replay::{LogEntry, Recorder};
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Strategy {
+    BreadthFirst,
+    DepthFirst,
+}
+
+/// What a decoder makes of the outputs that followed a move.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Step<O> {
+    /// The move succeeded, keep exploring from the new state.
+    Continue(O),
+    /// Record the observation but don't explore past it, e.g. a wall.
+    Stop(O),
+}
+
+pub struct Exploration<W: Word, S, O> {
+    pub observations: HashMap<S, O>,
+    parents: HashMap<S, S>,
+    /// The inputs and outputs of the move into every reached state.
+    moves: HashMap<S, Vec<LogEntry<W>>>,
+    /// The goal state that ended the exploration with the machine paused there.
+    pub goal: Option<(S, IntcodeVm<W>)>,
+    /// The machine exploration started from, still paused at the start.
+    pub root: IntcodeVm<W>,
+}
+
+impl<W: Word, S: Clone + Eq + Hash, O> Exploration<W, S, O> {
+    /// The states from the start up to and including `to`.
+    pub fn path(&self, to: &S) -> Option<Vec<S>> {
+        if !self.observations.contains_key(to) && !self.parents.values().any(|p| p == to) {
+            return None;
+        }
+        let mut path = vec![to.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Everything the machine read and wrote on the way from the start to
+    /// `to`, which replays how it got there.
+    pub fn log(&self, to: &S) -> Option<Vec<LogEntry<W>>> {
+        let path = self.path(to)?;
+        Some(path.iter().skip(1).flat_map(|s| self.moves[s].iter().cloned()).collect())
+    }
+}
+
+/// Runs one move and returns its inputs and outputs in the order the
+/// machine consumed and produced them.
+fn run_move<W: Word>(vm: &mut IntcodeVm<W>, inputs: &[W]) -> Result<Vec<LogEntry<W>>, IntcodeError<W>> {
+    inputs.iter().for_each(|i| vm.push_input(i.clone()));
+    let mut entries = Vec::new();
+    loop {
+        let next_input = vm.inputs.front().cloned();
+        let queued = vm.inputs.len();
+        match vm.step()? {
+            Some(Event::Output(o)) => entries.push(LogEntry::Output(o)),
+            Some(Event::NeedInput) | Some(Event::Halted) => return Ok(entries),
+            None if vm.inputs.len() < queued => entries.push(LogEntry::Input(next_input.unwrap())),
+            None => {}
+        }
+    }
+}
+
+fn outputs<W: Word>(entries: &[LogEntry<W>]) -> Vec<W> {
+    entries.iter()
+        .filter_map(|e| match e {
+            LogEntry::Output(o) => Some(o.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Explores from `start` with `vm` paused there. `moves` lists the inputs
+/// to try from a state and the state each leads to, `decode` interprets the
+/// outputs of a move. States are visited once; exploration stops at the
+/// first observation accepted by `is_goal` or when nothing is left.
+///
+/// Only the path to a goal is recorded, since the forks' moves don't form
+/// one stream. When `vm` records, the moves up to the goal are written to
+/// its log and the goal machine keeps recording. Without a goal nothing is
+/// written and `root` keeps the recorder.
+pub fn explore<W, S, O, M, D, G>(vm: IntcodeVm<W>, start: S, strategy: Strategy, moves: M, decode: D, is_goal: G)
+    -> Result<Exploration<W, S, O>, IntcodeError<W>>
+    where W: Word, S: Clone + Eq + Hash, M: Fn(&S) -> Vec<(Vec<W>, S)>, D: Fn(&[W]) -> Step<O>, G: Fn(&S, &O) -> bool {
+    let mut exploration = Exploration { observations: HashMap::new(), parents: HashMap::new(), moves: HashMap::new(), goal: None, root: vm };
+    let mut seen = HashSet::new();
+    seen.insert(start.clone());
+    let mut frontier = VecDeque::new();
+    frontier.push_back((start, exploration.root.clone()));
+
+    while let Some((state, vm)) = match strategy {
+        Strategy::BreadthFirst => frontier.pop_front(),
+        Strategy::DepthFirst => frontier.pop_back(),
+    } {
+        for (inputs, next) in moves(&state) {
+            if !seen.insert(next.clone()) {
+                continue;
+            }
+            let mut fork = vm.clone();
+            let entries = run_move(&mut fork, &inputs)?;
+            let step = decode(&outputs(&entries));
+            exploration.parents.insert(next.clone(), state.clone());
+            exploration.moves.insert(next.clone(), entries);
+            let (observation, expand) = match step {
+                Step::Continue(o) => (o, !fork.halted),
+                Step::Stop(o) => (o, false),
+            };
+            let reached_goal = is_goal(&next, &observation);
+            exploration.observations.insert(next.clone(), observation);
+            if reached_goal {
+                if let Some(recorder) = exploration.root.recorder.take() {
+                    fork.recorder = Some(recorder);
+                    for entry in exploration.log(&next).unwrap() {
+                        fork.record(|r| r.write(&entry))?;
+                    }
+                    fork.record(Recorder::flush)?;
+                }
+                exploration.goal = Some((next, fork));
+                return Ok(exploration);
+            }
+            if expand {
+                frontier.push_back((next, fork));
+            }
+        }
+    }
+    Ok(exploration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode_computer::replay;
+
+    // Walks a corridor of cells 0..=5: input 1 steps right, 2 steps left.
+    // Outputs 0 when blocked, 1 on a free cell, 2 on the exit at cell 5.
+    const CORRIDOR: &str = "3,100,1008,100,1,101,1005,101,23,1001,102,-1,103,1007,103,0,101,1005,101,37,1105,1,42,1001,102,1,103,1007,103,6,101,1006,101,37,1105,1,42,104,0,1105,1,0,1001,103,0,102,1008,102,5,101,1005,101,58,104,1,1105,1,0,104,2,1105,1,0";
+
+    fn corridor_moves(cell: &i64) -> Vec<(Vec<i64>, i64)> {
+        vec![(vec![1], cell + 1), (vec![2], cell - 1)]
+    }
+
+    fn decode(outputs: &[i64]) -> Step<i64> {
+        match outputs {
+            [0] => Step::Stop(0),
+            [o] => Step::Continue(*o),
+            _ => panic!("Unexpected outputs {:?}", outputs),
+        }
+    }
+
+    #[test]
+    fn test_explore_map() {
+        let program = crate::intcode_computer::read_program(CORRIDOR);
+        for strategy in [Strategy::BreadthFirst, Strategy::DepthFirst] {
+            let exploration = explore(IntcodeVm::new(&program), 0, strategy, corridor_moves, decode, |_, _| false).unwrap();
+            let mut observed: Vec<(i64, i64)> = exploration.observations.into_iter().collect();
+            observed.sort();
+            assert_eq!(observed, vec![(-1, 0), (1, 1), (2, 1), (3, 1), (4, 1), (5, 2), (6, 0)]);
+            assert!(exploration.goal.is_none());
+        }
+    }
+
+    #[test]
+    fn test_explore_goal() {
+        let program = crate::intcode_computer::read_program(CORRIDOR);
+        let exploration = explore(IntcodeVm::new(&program), 0, Strategy::BreadthFirst, corridor_moves, decode, |_, o| *o == 2).unwrap();
+        let (goal, mut vm) = exploration.goal.clone().unwrap();
+        assert_eq!(goal, 5);
+        assert_eq!(exploration.path(&goal), Some(vec![0, 1, 2, 3, 4, 5]));
+        assert_eq!(exploration.path(&17), None);
+        // The returned machine is paused at the goal.
+        assert_eq!(run_move(&mut vm, &[2]), Ok(vec![LogEntry::Input(2), LogEntry::Output(1)]));
+    }
+
+    #[test]
+    fn test_explore_records_path_to_goal() {
+        let program = crate::intcode_computer::read_program(CORRIDOR);
+        let log = std::env::temp_dir().join(format!("intcode-explore-{}.log", std::process::id()));
+        let vm = IntcodeVm::recording(&program, &[], &log).unwrap();
+        let exploration = explore(vm, 0, Strategy::DepthFirst, corridor_moves, decode, |_, o| *o == 2).unwrap();
+        let path_log = exploration.log(&5).unwrap();
+        let mut expected: Vec<LogEntry<i64>> = (0..4).flat_map(|_| vec![LogEntry::Input(1), LogEntry::Output(1)]).collect();
+        expected.extend(vec![LogEntry::Input(1), LogEntry::Output(2)]);
+        assert_eq!(path_log, expected);
+        assert_eq!(exploration.log(&0), Some(vec![]));
+        assert!(replay::replay_entries(&program, &path_log).is_ok());
+
+        // The goal machine goes on writing the same log.
+        let (_, mut vm) = exploration.goal.unwrap();
+        assert_eq!(vm.next(), None);
+        vm.push_input(2);
+        assert_eq!(vm.next(), Some(1));
+        drop(vm);
+        expected.extend(vec![LogEntry::Input(2), LogEntry::Output(1)]);
+        assert_eq!(replay::read_log::<i64>(&log).unwrap(), expected);
+        std::fs::remove_file(&log).ok();
+    }
+
+    #[test]
+    fn test_explore_without_goal_keeps_recorder() {
+        let program = crate::intcode_computer::read_program(CORRIDOR);
+        let log = std::env::temp_dir().join(format!("intcode-explore-map-{}.log", std::process::id()));
+        let vm = IntcodeVm::recording(&program, &[], &log).unwrap();
+        let exploration = explore(vm, 0, Strategy::BreadthFirst, corridor_moves, decode, |_, _| false).unwrap();
+        assert_eq!(exploration.observations.len(), 7);
+        assert_eq!(replay::read_log::<i64>(&log).unwrap(), vec![]);
+
+        // The root machine is still at the start and records what it does next.
+        let mut root = exploration.root;
+        assert_eq!(run_move(&mut root, &[2]), Ok(vec![LogEntry::Input(2), LogEntry::Output(0)]));
+        drop(root);
+        assert_eq!(replay::read_log::<i64>(&log).unwrap(), vec![LogEntry::Input(2), LogEntry::Output(0)]);
+        std::fs::remove_file(&log).ok();
+    }
+}
@@ -0,0 +1,167 @@
+//! C ABI for the Intcode machine, declared in `ffi/intcode.h`. Machines use
+//! 64-bit words and are handed out as opaque pointers that must be released
+//! with `intcode_free`.
+
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::ptr;
+use std::slice;
+
+use super::{Event, IntcodeVm};
+
+pub const INTCODE_ERROR: i32 = -1;
+pub const INTCODE_OUTPUT: i32 = 0;
+pub const INTCODE_NEED_INPUT: i32 = 1;
+pub const INTCODE_HALTED: i32 = 2;
+
+#[derive(Clone)]
+pub struct IntcodeHandle {
+    vm: IntcodeVm<i64>,
+    output: i64,
+    error: Option<CString>,
+}
+
+/// Creates a machine loaded with `len` words from `program`.
+///
+/// # Safety
+///
+/// `program` must point to `len` readable words, or be null when `len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_new(program: *const i64, len: usize) -> *mut IntcodeHandle {
+    let program = if len == 0 { &[] } else { slice::from_raw_parts(program, len) };
+    let handle = IntcodeHandle { vm: IntcodeVm::new(program), output: 0, error: None };
+    Box::into_raw(Box::new(handle))
+}
+
+/// # Safety
+///
+/// `vm` must be a live handle from `intcode_new` or `intcode_snapshot`.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_push_input(vm: *mut IntcodeHandle, value: i64) {
+    (*vm).vm.push_input(value);
+}
+
+/// Runs until the next output, input request or halt and returns the
+/// matching `INTCODE_*` code. After `INTCODE_ERROR` the machine is stuck
+/// and `intcode_error` describes why.
+///
+/// # Safety
+///
+/// `vm` must be a live handle from `intcode_new` or `intcode_snapshot`.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_run_until_event(vm: *mut IntcodeHandle) -> i32 {
+    let handle = &mut *vm;
+    if handle.error.is_some() {
+        return INTCODE_ERROR;
+    }
+    match handle.vm.run_until_event() {
+        Ok(Event::Output(value)) => {
+            handle.output = value;
+            INTCODE_OUTPUT
+        }
+        Ok(Event::NeedInput) => INTCODE_NEED_INPUT,
+        Ok(Event::Halted) => INTCODE_HALTED,
+        Err(e) => {
+            handle.error = CString::new(e.to_string()).ok();
+            INTCODE_ERROR
+        }
+    }
+}
+
+/// The value of the most recent `INTCODE_OUTPUT` event.
+///
+/// # Safety
+///
+/// `vm` must be a live handle from `intcode_new` or `intcode_snapshot`.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_output(vm: *const IntcodeHandle) -> i64 {
+    (*vm).output
+}
+
+/// The memory value at `address`, unset cells read as 0.
+///
+/// # Safety
+///
+/// `vm` must be a live handle from `intcode_new` or `intcode_snapshot`.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_read(vm: *const IntcodeHandle, address: i64) -> i64 {
+    (*vm).vm.read(&address)
+}
+
+/// The error message after `INTCODE_ERROR`, null otherwise. The string is
+/// owned by the machine.
+///
+/// # Safety
+///
+/// `vm` must be a live handle from `intcode_new` or `intcode_snapshot`.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_error(vm: *const IntcodeHandle) -> *const c_char {
+    (*vm).error.as_ref().map_or(ptr::null(), |e| e.as_ptr())
+}
+
+/// An independent copy of the machine, including queued inputs.
+///
+/// # Safety
+///
+/// `vm` must be a live handle from `intcode_new` or `intcode_snapshot`.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_snapshot(vm: *const IntcodeHandle) -> *mut IntcodeHandle {
+    Box::into_raw(Box::new((*vm).clone()))
+}
+
+/// Releases a machine. Null is ignored.
+///
+/// # Safety
+///
+/// `vm` must be null or a live handle, and is dangling afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_free(vm: *mut IntcodeHandle) {
+    if !vm.is_null() {
+        drop(Box::from_raw(vm));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn test_c_abi() {
+        let program = [3, 0, 4, 0, 99];
+        unsafe {
+            let vm = intcode_new(program.as_ptr(), program.len());
+            assert_eq!(intcode_run_until_event(vm), INTCODE_NEED_INPUT);
+            let copy = intcode_snapshot(vm);
+            intcode_push_input(vm, 7);
+            assert_eq!(intcode_run_until_event(vm), INTCODE_OUTPUT);
+            assert_eq!(intcode_output(vm), 7);
+            assert_eq!(intcode_run_until_event(vm), INTCODE_HALTED);
+            assert_eq!(intcode_read(vm, 0), 7);
+            intcode_free(vm);
+
+            assert_eq!(intcode_read(copy, 0), 3);
+            intcode_push_input(copy, 8);
+            assert_eq!(intcode_run_until_event(copy), INTCODE_OUTPUT);
+            assert_eq!(intcode_output(copy), 8);
+            intcode_free(copy);
+            intcode_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_c_abi_error() {
+        unsafe {
+            let vm = intcode_new(ptr::null(), 0);
+            assert!(intcode_error(vm).is_null());
+            intcode_free(vm);
+
+            let program = [42];
+            let vm = intcode_new(program.as_ptr(), program.len());
+            assert_eq!(intcode_run_until_event(vm), INTCODE_ERROR);
+            assert_eq!(CStr::from_ptr(intcode_error(vm)).to_str(), Ok("Invalid op code 42 at ip=0"));
+            assert_eq!(intcode_run_until_event(vm), INTCODE_ERROR);
+            intcode_free(vm);
+        }
+    }
+}
@@ -0,0 +1,334 @@
+//! Differential fuzzing of the Intcode backends. Random programs built from
+//! valid instructions run under an instruction budget on the interpreter and
+//! the compiled backend with every word type, and all runs must agree on
+//! events, errors and final memory.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+use num_bigint::BigInt;
+
+use super::{memory, Event, IntcodeError, IntcodeVm, Word};
+use super::compiler::{compile, CompiledVm};
+
+/// xorshift64*, good enough to pick instructions and reproducible by seed.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        low + self.below((high - low + 1) as u64) as i64
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FuzzFailure {
+    pub case: usize,
+    pub program: Vec<i64>,
+    pub inputs: Vec<i64>,
+    pub reason: String,
+}
+
+impl fmt::Display for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |values: &[i64]| values.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(",");
+        write!(f, "Case {}: {}\nprogram: {}\ninputs: {}", self.case, self.reason, join(&self.program), join(&self.inputs))
+    }
+}
+
+const SCRATCH: i64 = 16;
+
+fn parameter_count(op: i64) -> usize {
+    match op {
+        1 | 2 | 7 | 8 => 3,
+        5 | 6 => 2,
+        3 | 4 | 9 => 1,
+        _ => 0,
+    }
+}
+
+/// A program of `instructions` random instructions. Position parameters
+/// point into the program or a scratch area after it, jumps land on
+/// instruction starts, and now and then a write parameter is immediate.
+pub fn generate_program(rng: &mut Rng, instructions: usize) -> Vec<i64> {
+    const OPS: [i64; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 99];
+    let mut program = Vec::new();
+    let mut starts = Vec::new();
+    let mut jumps = Vec::new();
+    for _ in 0..instructions {
+        let op = OPS[rng.below(OPS.len() as u64) as usize];
+        let count = parameter_count(op);
+        let writes = |n: usize| matches!((op, n), (1, 2) | (2, 2) | (7, 2) | (8, 2) | (3, 0));
+        let mut modes = [0; 3];
+        for (n, mode) in modes.iter_mut().enumerate().take(count) {
+            *mode = if writes(n) && rng.below(20) != 0 { [0, 2][rng.below(2) as usize] } else { rng.below(3) as i64 };
+        }
+        starts.push(program.len());
+        program.push(op + 100 * modes[0] + 1000 * modes[1] + 10000 * modes[2]);
+        for (n, mode) in modes.iter().enumerate().take(count) {
+            if (op == 5 || op == 6) && n == 1 {
+                jumps.push((program.len(), *mode));
+            }
+            program.push(match mode {
+                1 => rng.range(-20, 20),
+                2 => rng.range(-4, 8),
+                _ => 0,
+            });
+        }
+    }
+    program.push(99);
+    let len = program.len() as i64;
+    // Position parameters are filled in once the program length is known.
+    for start in &starts {
+        let op = program[*start] % 100;
+        let mut modes = program[*start] / 100;
+        for n in 0..parameter_count(op) {
+            if modes % 10 == 0 {
+                program[start + 1 + n] = rng.range(0, len + SCRATCH - 1);
+            }
+            modes /= 10;
+        }
+    }
+    for (slot, mode) in jumps {
+        let target = starts[rng.below(starts.len() as u64) as usize] as i64;
+        if mode == 1 {
+            program[slot] = target;
+        } else if mode == 0 {
+            // Jump through a scratch cell holding the target.
+            let cell = len + rng.range(0, SCRATCH - 1);
+            program[slot] = cell;
+            program.resize((cell + 1) as usize, 0);
+            program[cell as usize] = target;
+        }
+    }
+    program
+}
+
+/// Everything observable about a run, with values rendered as text so runs
+/// with different word types can be compared.
+#[derive(PartialEq, Eq, Clone, Debug)]
+struct Outcome {
+    trace: Vec<String>,
+    memory: Vec<String>,
+    overflow: bool,
+}
+
+trait Backend<W: Word> {
+    fn step(&mut self) -> Result<Option<Event<W>>, IntcodeError<W>>;
+    fn push_input(&mut self, value: W);
+}
+
+impl<W: Word> Backend<W> for IntcodeVm<W> {
+    fn step(&mut self) -> Result<Option<Event<W>>, IntcodeError<W>> {
+        IntcodeVm::step(self)
+    }
+
+    fn push_input(&mut self, value: W) {
+        IntcodeVm::push_input(self, value)
+    }
+}
+
+impl<'a, W: Word> Backend<W> for CompiledVm<'a, W> {
+    fn step(&mut self) -> Result<Option<Event<W>>, IntcodeError<W>> {
+        CompiledVm::step(self)
+    }
+
+    fn push_input(&mut self, value: W) {
+        CompiledVm::push_input(self, value)
+    }
+}
+
+fn drive<W: Word, B: Backend<W>>(backend: &mut B, inputs: &[i64], budget: usize) -> (Vec<String>, bool) {
+    let mut inputs = inputs.iter();
+    let mut trace = Vec::new();
+    let mut overflow = false;
+    let mut steps = 0;
+    loop {
+        if steps == budget {
+            trace.push("budget".to_string());
+            break;
+        }
+        match backend.step() {
+            Ok(None) => {}
+            Ok(Some(Event::Output(value))) => trace.push(format!("out {}", value)),
+            Ok(Some(Event::NeedInput)) => match inputs.next() {
+                Some(value) => {
+                    backend.push_input(W::from_i64(*value));
+                    continue;
+                }
+                None => {
+                    trace.push("input exhausted".to_string());
+                    break;
+                }
+            },
+            Ok(Some(Event::Halted)) => {
+                trace.push("halt".to_string());
+                break;
+            }
+            Err(e) => {
+                overflow = matches!(e, IntcodeError::Overflow { .. });
+                trace.push(format!("error: {}", e));
+                break;
+            }
+        }
+        steps += 1;
+    }
+    (trace, overflow)
+}
+
+/// Checks the memory left behind: no negative addresses, the program image
+/// complete, and no cells outside it that weren't written.
+fn check_memory<W: Word>(memory: &HashMap<W, W>, image_len: usize, written: &HashSet<W>) -> Result<(), String> {
+    if let Some(address) = memory.keys().find(|a| **a < W::zero()) {
+        return Err(format!("negative address {} in memory", address));
+    }
+    let image_end = W::from_i64(image_len as i64);
+    if (0..image_len).any(|a| !memory.contains_key(&W::from_i64(a as i64))) {
+        return Err("program image cell missing from memory".to_string());
+    }
+    match memory.keys().find(|a| **a >= image_end && !written.contains(a)) {
+        Some(address) => Err(format!("cell {} present but never written", address)),
+        None => Ok(()),
+    }
+}
+
+fn interpret<W: Word>(program: &[W], inputs: &[i64], budget: usize) -> Result<Outcome, String> {
+    let mut vm = IntcodeVm::new(program);
+    let mut written = HashSet::new();
+    let mut tracked = Tracked { vm: &mut vm, written: &mut written };
+    let (trace, overflow) = drive(&mut tracked, inputs, budget);
+    let memory = vm.into_memory();
+    check_memory(&memory, program.len(), &written)?;
+    Ok(Outcome { trace, memory: render(&memory), overflow })
+}
+
+/// Records every address the interpreter writes to.
+struct Tracked<'a, W: Word> {
+    vm: &'a mut IntcodeVm<W>,
+    written: &'a mut HashSet<W>,
+}
+
+impl<'a, W: Word> Backend<W> for Tracked<'a, W> {
+    fn step(&mut self) -> Result<Option<Event<W>>, IntcodeError<W>> {
+        let event = self.vm.step();
+        if let Some(address) = self.vm.last_write.take() {
+            self.written.insert(address);
+        }
+        event
+    }
+
+    fn push_input(&mut self, value: W) {
+        self.vm.push_input(value)
+    }
+}
+
+fn run_compiled<W: Word>(program: &[W], inputs: &[i64], budget: usize) -> Result<Outcome, String> {
+    let compiled = compile(program);
+    let mut vm = compiled.start();
+    let (trace, overflow) = drive(&mut vm, inputs, budget);
+    Ok(Outcome { trace, memory: render(&vm.into_memory()), overflow })
+}
+
+fn render<W: Word>(memory: &HashMap<W, W>) -> Vec<String> {
+    memory::dump(memory).iter().map(|(a, v)| format!("{}: {}", a, v)).collect()
+}
+
+fn guarded<F: FnOnce() -> Result<Outcome, String>>(name: &str, run: F) -> Result<Outcome, String> {
+    match panic::catch_unwind(AssertUnwindSafe(run)) {
+        Ok(outcome) => outcome.map_err(|e| format!("{}: {}", name, e)),
+        Err(_) => Err(format!("{} panicked", name)),
+    }
+}
+
+fn compare(name: &str, expected: &Outcome, found: &Outcome) -> Result<(), String> {
+    if expected.trace != found.trace {
+        return Err(format!("{} trace differs: {:?} != {:?}", name, expected.trace, found.trace));
+    }
+    if expected.memory != found.memory {
+        return Err(format!("{} memory differs", name));
+    }
+    Ok(())
+}
+
+/// Runs one program on every backend and checks that they agree. Words are
+/// only compared across types when the narrower one didn't overflow.
+pub fn check_case(program: &[i64], inputs: &[i64], budget: usize) -> Result<(), String> {
+    let wide: Vec<i128> = program.iter().map(|v| i128::from(*v)).collect();
+    let big: Vec<BigInt> = program.iter().map(|v| BigInt::from(*v)).collect();
+
+    let reference = guarded("i128 interpreter", || interpret(&wide, inputs, budget))?;
+    compare("i128 compiled", &reference, &guarded("i128 compiled", || run_compiled(&wide, inputs, budget))?)?;
+
+    let narrow = guarded("i64 interpreter", || interpret(program, inputs, budget))?;
+    compare("i64 compiled", &narrow, &guarded("i64 compiled", || run_compiled(program, inputs, budget))?)?;
+    if !narrow.overflow {
+        compare("i64 interpreter", &reference, &narrow)?;
+    }
+
+    // Past an i128 overflow, repeated squaring makes BigInt runs explode.
+    if !reference.overflow {
+        let unbounded = guarded("BigInt interpreter", || interpret(&big, inputs, budget))?;
+        compare("BigInt compiled", &unbounded, &guarded("BigInt compiled", || run_compiled(&big, inputs, budget))?)?;
+        compare("BigInt interpreter", &reference, &unbounded)?;
+    }
+    Ok(())
+}
+
+/// Generates and checks `cases` programs from `seed`, stopping at the first
+/// disagreement.
+pub fn fuzz(seed: u64, cases: usize, budget: usize) -> Result<(), FuzzFailure> {
+    let mut rng = Rng::new(seed);
+    for case in 0..cases {
+        let instructions = rng.range(1, 30) as usize;
+        let program = generate_program(&mut rng, instructions);
+        let inputs: Vec<i64> = (0..rng.below(8)).map(|_| rng.range(-50, 50)).collect();
+        check_case(&program, &inputs, budget)
+            .map_err(|reason| FuzzFailure { case, program: program.clone(), inputs, reason })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode_computer::decode_modes;
+
+    #[test]
+    fn test_generated_programs_decode() {
+        let mut rng = Rng::new(7);
+        for _ in 0..100 {
+            let program = generate_program(&mut rng, 20);
+            assert!(program.contains(&99));
+            assert!(decode_modes(program[0]).is_some(), "{}", program[0]);
+        }
+    }
+
+    #[test]
+    fn test_check_case() {
+        assert_eq!(check_case(&[3, 0, 4, 0, 99], &[5], 100), Ok(()));
+        assert_eq!(check_case(&[1105, 1, 0], &[], 50), Ok(()));
+        assert_eq!(check_case(&[1102, 4_000_000_000, 4_000_000_000, 5, 4, 5, 99], &[], 100), Ok(()));
+    }
+
+    #[test]
+    fn test_fuzz() {
+        if let Err(failure) = fuzz(2019, 1000, 500) {
+            panic!("{}", failure);
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! Finds values for program cells that make a run hit a target, e.g. the
+//! Day 2 noun and verb. When a few probe runs show the observed value is
+//! affine in the unknowns the equation is solved directly, otherwise every
+//! assignment is tried in parallel.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use super::{apply_patches, Patch, Word};
+use super::batch::{self, BatchRun, Variant};
+use super::patch::PatchError;
+
+/// A program cell whose value is searched for within `range`.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Unknown<W> {
+    pub name: String,
+    pub address: usize,
+    pub expected: W,
+    pub range: RangeInclusive<i64>,
+}
+
+impl<W: Word> Unknown<W> {
+    pub fn new(name: &str, address: usize, expected: W, range: RangeInclusive<i64>) -> Unknown<W> {
+        Unknown { name: name.to_string(), address, expected, range }
+    }
+
+    fn patch(&self, value: i64) -> Patch<W> {
+        Patch::new(&self.name, self.address, self.expected.clone(), W::from_i64(value))
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Target<W> {
+    /// The final memory holds `value` at `address`.
+    Memory { address: W, value: W },
+    /// Output number `index` is `value`.
+    Output { index: usize, value: W },
+}
+
+impl<W: Word> Target<W> {
+    fn observe(&self, run: &BatchRun<W>) -> Option<W> {
+        match self {
+            Target::Memory { address, .. } => Some(run.memory.get(address).cloned().unwrap_or_else(W::zero)),
+            Target::Output { index, .. } => run.outputs.get(*index).cloned(),
+        }
+    }
+
+    fn value(&self) -> &W {
+        match self {
+            Target::Memory { value, .. } | Target::Output { value, .. } => value,
+        }
+    }
+
+    pub fn matches(&self, run: &BatchRun<W>) -> bool {
+        self.observe(run).as_ref() == Some(self.value())
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum InverseError<W> {
+    Patch(PatchError<W>),
+    /// The ranges hold more assignments than a search can count.
+    TooManyAssignments,
+}
+
+impl<W: fmt::Display> fmt::Display for InverseError<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InverseError::Patch(e) => write!(f, "{}", e),
+            InverseError::TooManyAssignments => write!(f, "Too many assignments to search"),
+        }
+    }
+}
+
+impl<W> From<PatchError<W>> for InverseError<W> {
+    fn from(e: PatchError<W>) -> InverseError<W> {
+        InverseError::Patch(e)
+    }
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Method {
+    Affine,
+    Search,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Solution {
+    pub values: Vec<i64>,
+    pub method: Method,
+}
+
+fn run<W: Word>(program: &[W], unknowns: &[Unknown<W>], values: &[i64]) -> Option<BatchRun<W>> {
+    let patches = unknowns.iter().zip(values).map(|(u, v)| u.patch(*v)).collect();
+    batch::run_variant(program, &Variant { patches, inputs: vec![] }).ok()
+}
+
+fn observe<W: Word>(program: &[W], unknowns: &[Unknown<W>], target: &Target<W>, values: &[i64]) -> Option<i128> {
+    let observed = target.observe(&run(program, unknowns, values)?)?;
+    observed.to_i64().map(i128::from)
+}
+
+/// Probes the program at the lower corner, one step along every unknown and
+/// a few points off the axes. Returns the value at the lower corner and the
+/// coefficient of every unknown if all probes agree with an affine model.
+fn affine_model<W: Word>(program: &[W], unknowns: &[Unknown<W>], target: &Target<W>) -> Option<(i128, Vec<i128>)> {
+    let low: Vec<i64> = unknowns.iter().map(|u| *u.range.start()).collect();
+    let base = observe(program, unknowns, target, &low)?;
+    let mut coefficients = Vec::with_capacity(unknowns.len());
+    for (i, u) in unknowns.iter().enumerate() {
+        if u.range.end() > u.range.start() {
+            let mut point = low.clone();
+            point[i] += 1;
+            coefficients.push(observe(program, unknowns, target, &point)? - base);
+        } else {
+            coefficients.push(0);
+        }
+    }
+
+    let mut probes = vec![unknowns.iter().map(|u| *u.range.end()).collect::<Vec<i64>>()];
+    probes.push(unknowns.iter().map(|u| ((i128::from(*u.range.start()) + i128::from(*u.range.end())) / 2) as i64).collect());
+    for i in 0..unknowns.len() {
+        for j in i + 1..unknowns.len() {
+            let mut point = low.clone();
+            point[i] = *unknowns[i].range.end();
+            point[j] = *unknowns[j].range.end();
+            probes.push(point);
+        }
+    }
+    for point in probes {
+        let predicted = point.iter().zip(&low).zip(&coefficients)
+            .map(|((p, l), c)| (i128::from(*p) - i128::from(*l)) * c)
+            .sum::<i128>() + base;
+        if observe(program, unknowns, target, &point)? != predicted {
+            return None;
+        }
+    }
+    Some((base, coefficients))
+}
+
+/// The first assignment in lexicographic order satisfying
+/// `base + sum(coefficients[i] * (values[i] - low[i])) == target`.
+fn solve_affine(unknowns: &[(i64, i64)], base: i128, coefficients: &[i128], target: i128) -> Option<Vec<i64>> {
+    let mut values: Vec<i64> = unknowns.iter().map(|(low, _)| *low).collect();
+    let last = match coefficients.iter().rposition(|c| *c != 0) {
+        Some(last) => last,
+        None => return if base == target { Some(values) } else { None },
+    };
+    let free: Vec<usize> = (0..last).filter(|i| coefficients[*i] != 0).collect();
+    loop {
+        let partial: i128 = free.iter().map(|&i| coefficients[i] * (i128::from(values[i]) - i128::from(unknowns[i].0))).sum();
+        let rest = target - base - partial;
+        if rest % coefficients[last] == 0 {
+            let offset = rest / coefficients[last];
+            let (low, high) = unknowns[last];
+            if offset >= 0 && offset <= i128::from(high) - i128::from(low) {
+                values[last] = (i128::from(low) + offset) as i64;
+                return Some(values);
+            }
+        }
+        // Next assignment of the free unknowns, last one fastest.
+        let mut k = free.len();
+        loop {
+            if k == 0 {
+                return None;
+            }
+            k -= 1;
+            let i = free[k];
+            if values[i] < unknowns[i].1 {
+                values[i] += 1;
+                break;
+            }
+            values[i] = unknowns[i].0;
+        }
+    }
+}
+
+/// Tries every assignment on `workers` threads and returns the first one in
+/// lexicographic order whose run satisfies `predicate`. Workers skip all
+/// assignments past the best match found so far.
+pub fn search<W, P>(program: &[W], unknowns: &[Unknown<W>], workers: usize, predicate: P) -> Result<Option<Vec<i64>>, InverseError<W>>
+    where W: Word, P: Fn(&BatchRun<W>) -> bool + Sync {
+    let sizes: Vec<usize> = unknowns.iter()
+        .map(|u| usize::try_from((i128::from(*u.range.end()) - i128::from(*u.range.start()) + 1).max(0)).ok())
+        .collect::<Option<Vec<usize>>>()
+        .ok_or(InverseError::TooManyAssignments)?;
+    let total = sizes.iter().try_fold(1usize, |total, size| total.checked_mul(*size)).ok_or(InverseError::TooManyAssignments)?;
+    let assignment = |mut index: usize| {
+        let mut values = vec![0; unknowns.len()];
+        for i in (0..unknowns.len()).rev() {
+            values[i] = (i128::from(*unknowns[i].range.start()) + (index % sizes[i]) as i128) as i64;
+            index /= sizes[i];
+        }
+        values
+    };
+    let found = batch::parallel_find_index(total, workers, |index| {
+        let values = assignment(index);
+        run(program, unknowns, &values).filter(|r| predicate(r)).map(|_| values)
+    });
+    Ok(found.map(|(_, values)| values))
+}
+
+/// Finds the first assignment of `unknowns` for which the run hits `target`.
+/// The probes can't rule out a branch between them, so an affine answer is
+/// checked by running it and search takes over when it fails or the affine
+/// equation has no solution.
+pub fn solve<W: Word>(program: &[W], unknowns: &[Unknown<W>], target: &Target<W>, workers: usize) -> Result<Option<Solution>, InverseError<W>> {
+    let mut checked = program.to_vec();
+    let low_patches: Vec<Patch<W>> = unknowns.iter().map(|u| u.patch(*u.range.start())).collect();
+    apply_patches(&mut checked, &low_patches)?;
+
+    let wanted = target.value().to_i64().map(i128::from);
+    if let (Some((base, coefficients)), Some(wanted)) = (affine_model(program, unknowns, target), wanted) {
+        let bounds: Vec<(i64, i64)> = unknowns.iter().map(|u| (*u.range.start(), *u.range.end())).collect();
+        if let Some(values) = solve_affine(&bounds, base, &coefficients, wanted) {
+            if run(program, unknowns, &values).is_some_and(|r| target.matches(&r)) {
+                return Ok(Some(Solution { values, method: Method::Affine }));
+            }
+        }
+    }
+    let values = search(program, unknowns, workers, |r| target.matches(r))?;
+    Ok(values.map(|values| Solution { values, method: Method::Search }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode_computer::read_program;
+
+    // Computes [0] = 7 * [17] + 3 * [18] + 5.
+    const LINEAR: &str = "1002,17,7,20,1002,18,3,21,1,20,21,0,1001,0,5,0,99,0,0";
+    // Outputs [7] * [8].
+    const PRODUCT: &str = "2,7,8,9,4,9,99,0,0";
+    // Computes [0] = [17] + [18], or 1000 * [18] when [17] is 37.
+    const BRANCH: &str = "1008,17,37,19,1005,19,12,1,17,18,0,99,1002,18,1000,0,99,0,0,0";
+
+    fn unknowns(address: usize, range: RangeInclusive<i64>) -> Vec<Unknown<i64>> {
+        vec![Unknown::new("a", address, 0, range.clone()), Unknown::new("b", address + 1, 0, range)]
+    }
+
+    #[test]
+    fn test_solve_affine() {
+        assert_eq!(solve_affine(&[(0, 99), (0, 99)], 5, &[7, 3], 5 + 7 * 40 + 3 * 2), Some(vec![1, 93]));
+        assert_eq!(solve_affine(&[(0, 9), (0, 9)], 5, &[7, 3], 5 + 7 * 4 + 3 * 2), Some(vec![1, 9]));
+        assert_eq!(solve_affine(&[(0, 9), (0, 9)], 0, &[10, 0], 35), None);
+        assert_eq!(solve_affine(&[(0, 9), (0, 9)], 0, &[0, 0], 0), Some(vec![0, 0]));
+    }
+
+    #[test]
+    fn test_solve_detects_affine_programs() {
+        let program: Vec<i64> = read_program(LINEAR);
+        let target = Target::Memory { address: 0, value: 7 * 12 + 3 * 40 + 5 };
+        let solution = solve(&program, &unknowns(17, 0..=99), &target, 4).unwrap().unwrap();
+        assert_eq!(solution.method, Method::Affine);
+        let [a, b] = [solution.values[0], solution.values[1]];
+        assert_eq!(7 * a + 3 * b + 5, 7 * 12 + 3 * 40 + 5);
+        assert_eq!(solution.values, vec![0, 68]);
+        let unreachable = Target::Memory { address: 0, value: 4 };
+        assert_eq!(solve(&program, &unknowns(17, 0..=99), &unreachable, 4), Ok(None));
+    }
+
+    #[test]
+    fn test_solve_falls_back_to_search() {
+        let program: Vec<i64> = read_program(PRODUCT);
+        let target = Target::Output { index: 0, value: 391 };
+        let solution = solve(&program, &unknowns(7, 0..=99), &target, 4).unwrap().unwrap();
+        assert_eq!(solution, Solution { values: vec![17, 23], method: Method::Search });
+        let wrong_guard = vec![Unknown::new("a", 7, 5, 0..=9)];
+        assert!(solve(&program, &wrong_guard, &target, 4).is_err());
+    }
+
+    #[test]
+    fn test_solve_searches_past_affine_probes() {
+        // Every probe misses the branch, so the model looks like a + b.
+        let program: Vec<i64> = read_program(BRANCH);
+        let target = Target::Memory { address: 0, value: 5000 };
+        let solution = solve(&program, &unknowns(17, 0..=99), &target, 4).unwrap().unwrap();
+        assert_eq!(solution, Solution { values: vec![37, 5], method: Method::Search });
+        let sum = Target::Memory { address: 0, value: 150 };
+        assert_eq!(solve(&program, &unknowns(17, 0..=99), &sum, 4).unwrap().unwrap().values, vec![51, 99]);
+    }
+
+    #[test]
+    fn test_search_covers_wide_ranges_lazily() {
+        // 10^12 assignments, far too many to collect, with an early match.
+        let program: Vec<i64> = read_program(LINEAR);
+        let wide = vec![Unknown::new("a", 17, 0, 0..=999_999), Unknown::new("b", 18, 0, 0..=999_999)];
+        let found = search(&program, &wide, 4, |r| r.memory[&0] == 3 * 20 + 5);
+        assert_eq!(found, Ok(Some(vec![0, 20])));
+    }
+
+    #[test]
+    fn test_full_word_ranges() {
+        // Copies [5] to [0].
+        let program: Vec<i64> = read_program("1001,5,0,0,99,0");
+        let full = vec![Unknown::new("a", 5, 0, i64::MIN..=i64::MAX)];
+        let target = Target::Memory { address: 0, value: -5 };
+        assert_eq!(solve(&program, &full, &target, 4), Ok(Some(Solution { values: vec![-5], method: Method::Affine })));
+        assert_eq!(search(&program, &full, 4, |_| true), Err(InverseError::TooManyAssignments));
+        let squared = vec![Unknown::new("a", 5, 0, 0..=1 << 40), Unknown::new("b", 0, 1001, 0..=1 << 40)];
+        assert_eq!(search(&program, &squared, 4, |_| true), Err(InverseError::TooManyAssignments));
+        let last = vec![Unknown::new("a", 5, 0, i64::MIN..=i64::MIN + 9)];
+        let found = search(&program, &last, 4, |r| r.memory[&0] == i64::MIN + 9);
+        assert_eq!(found, Ok(Some(vec![i64::MIN + 9])));
+    }
+}
@@ -0,0 +1,108 @@
+//! Listings and diffs of the memory a run leaves behind. Unset cells read as
+//! zero, so a zero and a missing cell compare equal.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use super::Word;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CellDiff<W> {
+    pub address: W,
+    pub before: W,
+    pub after: W,
+    /// The cell lies past the end of the loaded program.
+    pub beyond_image: bool,
+}
+
+impl<W: fmt::Display> fmt::Display for CellDiff<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = if self.beyond_image { '+' } else { '~' };
+        write!(f, "{} {}: {} -> {}", marker, self.address, self.before, self.after)
+    }
+}
+
+pub fn program_memory<W: Word>(program: &[W]) -> HashMap<W, W> {
+    program.iter()
+        .enumerate()
+        .map(|(i, v)| (W::from_i64(i as i64), v.clone()))
+        .collect()
+}
+
+/// All non-zero cells ordered by address.
+pub fn dump<W: Word>(memory: &HashMap<W, W>) -> Vec<(W, W)> {
+    let mut cells: Vec<(W, W)> = memory.iter()
+        .filter(|(_, v)| **v != W::zero())
+        .map(|(a, v)| (a.clone(), v.clone()))
+        .collect();
+    cells.sort();
+    cells
+}
+
+pub fn format_dump<W: Word>(memory: &HashMap<W, W>) -> String {
+    dump(memory).iter()
+        .map(|(a, v)| format!("{}: {}\n", a, v))
+        .collect()
+}
+
+/// The cells that differ between two memories, ordered by address. Cells at
+/// or past `image_len` are marked as beyond the program image.
+pub fn diff<W: Word>(before: &HashMap<W, W>, after: &HashMap<W, W>, image_len: usize) -> Vec<CellDiff<W>> {
+    let image_end = W::from_i64(image_len as i64);
+    let read = |memory: &HashMap<W, W>, address: &W| memory.get(address).cloned().unwrap_or_else(W::zero);
+    let addresses: BTreeSet<&W> = before.keys().chain(after.keys()).collect();
+    addresses.into_iter()
+        .map(|a| CellDiff { address: a.clone(), before: read(before, a), after: read(after, a), beyond_image: *a >= image_end })
+        .filter(|c| c.before != c.after)
+        .collect()
+}
+
+/// What a run changed compared to the program it was started with.
+pub fn diff_program<W: Word>(program: &[W], memory: &HashMap<W, W>) -> Vec<CellDiff<W>> {
+    diff(&program_memory(program), memory, program.len())
+}
+
+pub fn format_diff<W: Word>(cells: &[CellDiff<W>]) -> String {
+    cells.iter().map(|c| format!("{}\n", c)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode_computer::{read_program, IntcodeVm};
+
+    fn run(program: &[i64]) -> HashMap<i64, i64> {
+        let mut vm = IntcodeVm::new(program);
+        vm.by_ref().for_each(drop);
+        vm.into_memory()
+    }
+
+    #[test]
+    fn test_dump() {
+        let memory = run(&read_program("1101,2,3,7,99,0,0,0"));
+        assert_eq!(dump(&memory), vec![(0, 1101), (1, 2), (2, 3), (3, 7), (4, 99), (7, 5)]);
+        assert_eq!(format_dump(&program_memory::<i64>(&[1, 0, 2])), "0: 1\n2: 2\n");
+    }
+
+    #[test]
+    fn test_diff_program() {
+        let program: Vec<i64> = read_program("1101,2,3,3,1101,4,5,20,99");
+        let cells = diff_program(&program, &run(&program));
+        assert_eq!(cells, vec![
+            CellDiff { address: 3, before: 3, after: 5, beyond_image: false },
+            CellDiff { address: 20, before: 0, after: 9, beyond_image: true },
+        ]);
+        assert_eq!(format_diff(&cells), "~ 3: 3 -> 5\n+ 20: 0 -> 9\n");
+    }
+
+    #[test]
+    fn test_diff_runs() {
+        let first = run(&read_program("1101,2,3,7,99,0,0,0"));
+        let second = run(&read_program("1101,2,4,7,99,0,0,0"));
+        assert_eq!(diff(&first, &second, 8), vec![
+            CellDiff { address: 2, before: 3, after: 4, beyond_image: false },
+            CellDiff { address: 7, before: 5, after: 6, beyond_image: false },
+        ]);
+        assert!(diff(&first, &first, 8).is_empty());
+    }
+}
@@ -0,0 +1,471 @@
+pub mod batch;
+pub mod compiler;
+pub mod explore;
+pub mod ffi;
+pub mod fuzz;
+pub mod inverse;
+pub mod memory;
+pub mod patch;
+pub mod replay;
+pub mod word;
+#[cfg(test)]
+mod conformance;
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::sync::mpsc::{Sender, Receiver};
+
+pub use self::patch::{Patch, apply_patches};
+pub use self::word::Word;
+
+use self::patch::PatchError;
+
+use self::replay::{LogEntry, Recorder, ReplayError};
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Event<W> {
+    Output(W),
+    NeedInput,
+    Halted,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum IntcodeError<W> {
+    Overflow { ip: W },
+    InvalidOpcode { ip: W, instruction: W },
+    InvalidMode { ip: W, instruction: W },
+    NegativeAddress { ip: W, address: W },
+    InputClosed { ip: W },
+    Log { ip: W, kind: io::ErrorKind },
+}
+
+impl<W: fmt::Display> fmt::Display for IntcodeError<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntcodeError::Overflow { ip } => write!(f, "Overflow at ip={}", ip),
+            IntcodeError::InvalidOpcode { ip, instruction } => write!(f, "Invalid op code {} at ip={}", instruction, ip),
+            IntcodeError::InvalidMode { ip, instruction } => write!(f, "Invalid mode in {} at ip={}", instruction, ip),
+            IntcodeError::NegativeAddress { ip, address } => write!(f, "Negative address {} at ip={}", address, ip),
+            IntcodeError::InputClosed { ip } => write!(f, "Input closed at ip={}", ip),
+            IntcodeError::Log { ip, kind } => write!(f, "Failed to write log at ip={}: {}", ip, kind),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+enum Mode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+#[derive(Debug)]
+pub struct IntcodeVm<W: Word = i128> {
+    memory: HashMap<W, W>,
+    ip: W,
+    relative_base: W,
+    inputs: VecDeque<W>,
+    halted: bool,
+    last_write: Option<W>,
+    recorder: Option<Recorder>,
+}
+
+/// A clone starts without a recorder, so forks of a recorded machine never
+/// write into the same log.
+impl<W: Word> Clone for IntcodeVm<W> {
+    fn clone(&self) -> IntcodeVm<W> {
+        IntcodeVm {
+            memory: self.memory.clone(),
+            ip: self.ip.clone(),
+            relative_base: self.relative_base.clone(),
+            inputs: self.inputs.clone(),
+            halted: self.halted,
+            last_write: self.last_write.clone(),
+            recorder: None,
+        }
+    }
+}
+
+impl<W: Word> IntcodeVm<W> {
+    pub fn new(program: &[W]) -> IntcodeVm<W> {
+        IntcodeVm::load(program, None)
+    }
+
+    /// Loads `program` with `patches` applied.
+    pub fn patched(program: &[W], patches: &[Patch<W>]) -> Result<IntcodeVm<W>, PatchError<W>> {
+        let mut patched = program.to_vec();
+        apply_patches(&mut patched, patches)?;
+        Ok(IntcodeVm::load(&patched, None))
+    }
+
+    /// Like `patched`, recording the run to `log`. The log lists the patches
+    /// at its top, so a replay can apply them again.
+    pub fn recording(program: &[W], patches: &[Patch<W>], log: &Path) -> Result<IntcodeVm<W>, ReplayError<W>> {
+        let mut patched = program.to_vec();
+        apply_patches(&mut patched, patches)?;
+        Ok(IntcodeVm::load(&patched, Some(Recorder::create(log, patches)?)))
+    }
+
+    fn load(program: &[W], recorder: Option<Recorder>) -> IntcodeVm<W> {
+        IntcodeVm {
+            memory: memory::program_memory(program),
+            ip: W::zero(),
+            relative_base: W::zero(),
+            inputs: VecDeque::new(),
+            halted: false,
+            last_write: None,
+            recorder,
+        }
+    }
+
+    pub fn push_input(&mut self, value: W) {
+        self.inputs.push_back(value);
+    }
+
+    pub fn with_inputs<I: IntoIterator<Item = W>>(mut self, inputs: I) -> IntcodeVm<W> {
+        self.inputs.extend(inputs);
+        self
+    }
+
+    /// Groups the outputs into vectors of `size`, e.g. coordinate triples.
+    pub fn chunks(self, size: usize) -> Chunks<W> {
+        Chunks { vm: self, size, pending: Vec::new() }
+    }
+
+    /// Whether the machine is stopped at an input instruction with no
+    /// input queued.
+    pub fn needs_input(&self) -> bool {
+        !self.halted && self.inputs.is_empty() && self.read(&self.ip).to_i64().map(|i| i % 100) == Some(3)
+    }
+
+    pub fn into_memory(self) -> HashMap<W, W> {
+        self.memory
+    }
+
+    pub fn ip(&self) -> &W {
+        &self.ip
+    }
+
+    pub fn read(&self, address: &W) -> W {
+        self.memory.get(address).cloned().unwrap_or_else(W::zero)
+    }
+
+    pub fn run_until_event(&mut self) -> Result<Event<W>, IntcodeError<W>> {
+        loop {
+            if let Some(event) = self.step()? {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Executes one instruction. Returns the event it produced, if any; on
+    /// `NeedInput` the instruction is retried once input has been pushed.
+    pub fn step(&mut self) -> Result<Option<Event<W>>, IntcodeError<W>> {
+        if self.halted {
+            return Ok(Some(Event::Halted));
+        }
+        let instruction = self.read(&self.ip);
+        let (op, modes) = self.decode(&instruction)?;
+        match op {
+            1 | 2 => {
+                let a = self.parameter(1, modes)?;
+                let b = self.parameter(2, modes)?;
+                let res = if op == 1 { a.checked_add(&b) } else { a.checked_mul(&b) };
+                let res = res.ok_or_else(|| self.overflow())?;
+                self.write_parameter(3, modes, &instruction, res)?;
+                self.advance(4)?;
+            }
+            3 => {
+                if self.inputs.is_empty() {
+                    return Ok(Some(self.pause(Event::NeedInput)?));
+                }
+                let address = self.write_address(1, modes, &instruction)?;
+                let value = self.take_input()?;
+                self.write(address, value);
+                self.advance(2)?;
+            }
+            4 => {
+                let value = self.parameter(1, modes)?;
+                self.advance(2)?;
+                return Ok(Some(self.output(value)?));
+            }
+            5 | 6 => {
+                let a = self.parameter(1, modes)?;
+                let b = self.parameter(2, modes)?;
+                if (a != W::zero()) == (op == 5) {
+                    self.ip = b;
+                } else {
+                    self.advance(3)?;
+                }
+            }
+            7 | 8 => {
+                let a = self.parameter(1, modes)?;
+                let b = self.parameter(2, modes)?;
+                let check = if op == 7 { a < b } else { a == b };
+                let res = if check { W::one() } else { W::zero() };
+                self.write_parameter(3, modes, &instruction, res)?;
+                self.advance(4)?;
+            }
+            9 => {
+                let a = self.parameter(1, modes)?;
+                self.relative_base = self.relative_base.checked_add(&a).ok_or_else(|| self.overflow())?;
+                self.advance(2)?;
+            }
+            99 => {
+                self.halted = true;
+                return Ok(Some(self.pause(Event::Halted)?));
+            }
+            _ => return Err(IntcodeError::InvalidOpcode { ip: self.ip.clone(), instruction }),
+        }
+        Ok(None)
+    }
+
+    /// Pops the next queued input, which must exist, and records it.
+    fn take_input(&mut self) -> Result<W, IntcodeError<W>> {
+        let value = self.inputs.pop_front().unwrap();
+        self.record(|r| r.write(&LogEntry::Input(value.clone())))?;
+        Ok(value)
+    }
+
+    fn output(&mut self, value: W) -> Result<Event<W>, IntcodeError<W>> {
+        self.record(|r| r.write(&LogEntry::Output(value.clone())))?;
+        Ok(Event::Output(value))
+    }
+
+    /// Flushes the log before handing control back for input or at the end.
+    fn pause(&mut self, event: Event<W>) -> Result<Event<W>, IntcodeError<W>> {
+        self.record(Recorder::flush)?;
+        Ok(event)
+    }
+
+    fn record<F: FnOnce(&mut Recorder) -> io::Result<()>>(&mut self, f: F) -> Result<(), IntcodeError<W>> {
+        match self.recorder.as_mut().map(f) {
+            Some(Err(e)) => Err(IntcodeError::Log { ip: self.ip.clone(), kind: e.kind() }),
+            _ => Ok(()),
+        }
+    }
+
+    fn overflow(&self) -> IntcodeError<W> {
+        IntcodeError::Overflow { ip: self.ip.clone() }
+    }
+
+    fn decode(&self, instruction: &W) -> Result<(i64, [Mode; 3]), IntcodeError<W>> {
+        let invalid_opcode = || IntcodeError::InvalidOpcode { ip: self.ip.clone(), instruction: instruction.clone() };
+        let raw = instruction.to_i64().filter(|i| *i >= 0).ok_or_else(invalid_opcode)?;
+        let invalid_mode = || IntcodeError::InvalidMode { ip: self.ip.clone(), instruction: instruction.clone() };
+        let modes = decode_modes(raw).ok_or_else(invalid_mode)?;
+        Ok((raw % 100, modes))
+    }
+
+    fn write(&mut self, address: W, value: W) {
+        self.last_write = Some(address.clone());
+        self.memory.insert(address, value);
+    }
+
+    fn advance(&mut self, steps: i64) -> Result<(), IntcodeError<W>> {
+        self.ip = self.ip.checked_add(&W::from_i64(steps)).ok_or_else(|| self.overflow())?;
+        Ok(())
+    }
+
+    fn parameter_address(&self, n: usize, modes: [Mode; 3]) -> Result<W, IntcodeError<W>> {
+        let slot = self.ip.checked_add(&W::from_i64(n as i64)).ok_or_else(|| self.overflow())?;
+        let address = match modes[n - 1] {
+            Mode::Position => self.read(&slot),
+            Mode::Immediate => slot,
+            Mode::Relative => self.read(&slot).checked_add(&self.relative_base).ok_or_else(|| self.overflow())?,
+        };
+        if address < W::zero() {
+            return Err(IntcodeError::NegativeAddress { ip: self.ip.clone(), address });
+        }
+        Ok(address)
+    }
+
+    fn parameter(&self, n: usize, modes: [Mode; 3]) -> Result<W, IntcodeError<W>> {
+        let address = self.parameter_address(n, modes)?;
+        Ok(self.read(&address))
+    }
+
+    fn write_address(&self, n: usize, modes: [Mode; 3], instruction: &W) -> Result<W, IntcodeError<W>> {
+        if modes[n - 1] == Mode::Immediate {
+            return Err(IntcodeError::InvalidMode { ip: self.ip.clone(), instruction: instruction.clone() });
+        }
+        self.parameter_address(n, modes)
+    }
+
+    fn write_parameter(&mut self, n: usize, modes: [Mode; 3], instruction: &W, value: W) -> Result<(), IntcodeError<W>> {
+        let address = self.write_address(n, modes, instruction)?;
+        self.write(address, value);
+        Ok(())
+    }
+}
+
+/// Yields outputs until the machine halts or waits for input that has not
+/// been pushed. Panics on an Intcode error, like `run_program`.
+impl<W: Word> Iterator for IntcodeVm<W> {
+    type Item = W;
+
+    fn next(&mut self) -> Option<W> {
+        match self.run_until_event() {
+            Ok(Event::Output(value)) => Some(value),
+            Ok(_) => None,
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+pub struct Chunks<W: Word> {
+    vm: IntcodeVm<W>,
+    size: usize,
+    pending: Vec<W>,
+}
+
+impl<W: Word> Chunks<W> {
+    pub fn push_input(&mut self, value: W) {
+        self.vm.push_input(value);
+    }
+
+    pub fn needs_input(&self) -> bool {
+        self.vm.needs_input()
+    }
+}
+
+/// Never yields an incomplete chunk; outputs of a chunk interrupted by an
+/// input request are kept until it is completed.
+impl<W: Word> Iterator for Chunks<W> {
+    type Item = Vec<W>;
+
+    fn next(&mut self) -> Option<Vec<W>> {
+        while self.pending.len() < self.size {
+            self.pending.push(self.vm.next()?);
+        }
+        Some(self.pending.drain(..).collect())
+    }
+}
+
+fn decode_modes(raw: i64) -> Option<[Mode; 3]> {
+    let mut modes = [Mode::Position; 3];
+    let mut rest = raw / 100;
+    for mode in modes.iter_mut() {
+        *mode = match rest % 10 {
+            0 => Mode::Position,
+            1 => Mode::Immediate,
+            2 => Mode::Relative,
+            _ => return None,
+        };
+        rest /= 10;
+    }
+    Some(modes)
+}
+
+pub fn read_program<W: Word>(raw: &str) -> Vec<W> {
+    raw.split(',')
+        .map(|s| s.trim().parse::<W>().unwrap_or_else(|_| panic!("Invalid program value {}", s)))
+        .collect()
+}
+
+pub fn try_run_program<W: Word>(input: Receiver<W>, output: Sender<W>, program: &[W]) -> Result<(W, HashMap<W, W>), IntcodeError<W>> {
+    drive(IntcodeVm::new(program), input, output)
+}
+
+/// Runs `vm` until it halts, taking inputs from `input` whenever it waits.
+fn drive<W: Word>(mut vm: IntcodeVm<W>, input: Receiver<W>, output: Sender<W>) -> Result<(W, HashMap<W, W>), IntcodeError<W>> {
+    let mut io = W::zero();
+    loop {
+        match vm.run_until_event()? {
+            Event::Output(value) => {
+                io = value.clone();
+                output.send(value).ok();
+            }
+            Event::NeedInput => match input.recv() {
+                Ok(value) => vm.push_input(value),
+                Err(_) => return Err(IntcodeError::InputClosed { ip: vm.ip().clone() }),
+            },
+            Event::Halted => return Ok((io, vm.into_memory())),
+        }
+    }
+}
+
+pub fn run_program(input: Receiver<i128>, output: Sender<i128>, program: &[i128]) -> (i128, HashMap<i128, i128>) {
+    try_run_program(input, output, program).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Like `run_program` with `patches` applied first, see `IntcodeVm::patched`.
+pub fn run_patched_program(input: Receiver<i128>, output: Sender<i128>, program: &[i128], patches: &[Patch<i128>]) -> (i128, HashMap<i128, i128>) {
+    let vm = IntcodeVm::patched(program, patches).unwrap_or_else(|e| panic!("{}", e));
+    drive(vm, input, output).unwrap_or_else(|e| panic!("{}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    fn run_all<W: Word>(program: &[W], inputs: Vec<W>) -> Result<Vec<W>, IntcodeError<W>> {
+        let mut vm = IntcodeVm::new(program);
+        inputs.into_iter().for_each(|i| vm.push_input(i));
+        let mut outputs = Vec::new();
+        loop {
+            match vm.run_until_event()? {
+                Event::Output(o) => outputs.push(o),
+                _ => return Ok(outputs),
+            }
+        }
+    }
+
+    #[test]
+    fn test_word_types() {
+        let raw = "1102,34915192,34915192,7,4,7,99,0";
+        assert_eq!(run_all::<i64>(&read_program(raw), vec![]), Ok(vec![1219070632396864]));
+        assert_eq!(run_all::<i128>(&read_program(raw), vec![]), Ok(vec![1219070632396864]));
+        assert_eq!(run_all::<BigInt>(&read_program(raw), vec![]), Ok(vec![BigInt::from(1219070632396864_i64)]));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let raw = "1102,4611686018427387904,2,7,4,7,99,0";
+        assert_eq!(run_all::<i64>(&read_program(raw), vec![]), Err(IntcodeError::Overflow { ip: 0 }));
+        assert_eq!(run_all::<i128>(&read_program(raw), vec![]), Ok(vec![9223372036854775808]));
+        let raw = "101,1,9,9,1,9,9,9,99,9223372036854775806";
+        assert_eq!(run_all::<i64>(&read_program(raw), vec![]), Err(IntcodeError::Overflow { ip: 4 }));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(run_all::<i64>(&[42], vec![]), Err(IntcodeError::InvalidOpcode { ip: 0, instruction: 42 }));
+        assert_eq!(run_all::<i64>(&[103, 0, 99], vec![1]), Err(IntcodeError::InvalidMode { ip: 0, instruction: 103 }));
+        assert_eq!(run_all::<i64>(&[4, -1, 99], vec![]), Err(IntcodeError::NegativeAddress { ip: 0, address: -1 }));
+    }
+
+    #[test]
+    fn test_iterator() {
+        let program: Vec<i64> = read_program("109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99");
+        assert_eq!(IntcodeVm::new(&program).collect::<Vec<i64>>(), program);
+        let doubled: Vec<i64> = IntcodeVm::new(&[3, 9, 1002, 9, 2, 9, 4, 9, 99, 0]).with_inputs([21]).collect();
+        assert_eq!(doubled, vec![42]);
+    }
+
+    #[test]
+    fn test_chunks() {
+        let mut chunks = IntcodeVm::new(&[104_i64, 1, 104, 2, 104, 3, 3, 0, 104, 4, 99]).chunks(2);
+        assert_eq!(chunks.next(), Some(vec![1, 2]));
+        assert_eq!(chunks.next(), None);
+        assert!(chunks.needs_input());
+        chunks.push_input(0);
+        assert!(!chunks.needs_input());
+        assert_eq!(chunks.next(), Some(vec![3, 4]));
+        assert_eq!(chunks.next(), None);
+        assert!(!chunks.needs_input());
+    }
+
+    #[test]
+    fn test_need_input() {
+        let mut vm: IntcodeVm<i64> = IntcodeVm::new(&[3, 0, 4, 0, 99]);
+        assert_eq!(vm.run_until_event(), Ok(Event::NeedInput));
+        vm.push_input(7);
+        assert_eq!(vm.run_until_event(), Ok(Event::Output(7)));
+        assert_eq!(vm.run_until_event(), Ok(Event::Halted));
+        assert_eq!(vm.run_until_event(), Ok(Event::Halted));
+    }
+}
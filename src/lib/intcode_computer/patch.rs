@@ -0,0 +1,88 @@
+use std::fmt;
+
+use super::Word;
+
+/// A named write into a loaded program, applied before it runs. The value
+/// expected at the address guards against running with a different input.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Patch<W> {
+    pub name: String,
+    pub address: usize,
+    pub expected: W,
+    pub value: W,
+}
+
+impl<W: Word> Patch<W> {
+    pub fn new(name: &str, address: usize, expected: W, value: W) -> Patch<W> {
+        Patch { name: name.to_string(), address, expected, value }
+    }
+}
+
+impl<W: fmt::Display> fmt::Display for Patch<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: [{}] {} -> {}", self.name, self.address, self.expected, self.value)
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum PatchError<W> {
+    OutOfBounds { name: String, address: usize, len: usize },
+    Mismatch { name: String, address: usize, expected: W, found: W },
+}
+
+impl<W: fmt::Display> fmt::Display for PatchError<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::OutOfBounds { name, address, len } =>
+                write!(f, "Patch '{}' at {} is outside program of length {}", name, address, len),
+            PatchError::Mismatch { name, address, expected, found } =>
+                write!(f, "Patch '{}' expected {} at {} but found {}", name, expected, address, found),
+        }
+    }
+}
+
+/// Applies all patches or none of them.
+pub fn apply_patches<W: Word>(program: &mut [W], patches: &[Patch<W>]) -> Result<(), PatchError<W>> {
+    for patch in patches {
+        match program.get(patch.address) {
+            None => return Err(PatchError::OutOfBounds { name: patch.name.clone(), address: patch.address, len: program.len() }),
+            Some(found) if *found != patch.expected => return Err(PatchError::Mismatch {
+                name: patch.name.clone(),
+                address: patch.address,
+                expected: patch.expected.clone(),
+                found: found.clone(),
+            }),
+            _ => {}
+        }
+    }
+    for patch in patches {
+        program[patch.address] = patch.value.clone();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_patches() {
+        let mut program: Vec<i128> = vec![1, 0, 0, 3, 99];
+        let patches = [Patch::new("noun", 1, 0, 12), Patch::new("verb", 2, 0, 2)];
+        assert_eq!(apply_patches(&mut program, &patches), Ok(()));
+        assert_eq!(program, vec![1, 12, 2, 3, 99]);
+        assert_eq!(patches[0].to_string(), "noun: [1] 0 -> 12");
+    }
+
+    #[test]
+    fn test_rejected_patches_leave_program_untouched() {
+        let mut program: Vec<i128> = vec![1, 0, 0, 3, 99];
+        let mismatch = [Patch::new("noun", 1, 0, 12), Patch::new("wake up", 0, 2, 2)];
+        assert_eq!(apply_patches(&mut program, &mismatch),
+                   Err(PatchError::Mismatch { name: "wake up".to_string(), address: 0, expected: 2, found: 1 }));
+        let out_of_bounds = [Patch::new("far", 5, 0, 1)];
+        assert_eq!(apply_patches(&mut program, &out_of_bounds),
+                   Err(PatchError::OutOfBounds { name: "far".to_string(), address: 5, len: 5 }));
+        assert_eq!(program, vec![1, 0, 0, 3, 99]);
+    }
+}
@@ -0,0 +1,310 @@
+//! Recording of the input and output streams of Intcode runs, and
+//! deterministic replay of such a log against a program.
+//!
+//! A log has one entry per line in the order the machine saw them:
+//! `in <value>` when an input was consumed, `out <value>` when an output was
+//! produced. It starts with a `patch <address> <expected> <value> <name>`
+//! line for every patch applied to the program before the run.
+//!
+//! Only machines created with `IntcodeVm::recording` write a log. A log that
+//! can't be written stops the machine with `IntcodeError::Log`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc::{Sender, Receiver};
+
+use super::{apply_patches, drive, Event, IntcodeError, IntcodeVm, Patch, Word};
+use super::patch::PatchError;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum LogEntry<W> {
+    Patch(Patch<W>),
+    Input(W),
+    Output(W),
+}
+
+impl<W: fmt::Display> fmt::Display for LogEntry<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogEntry::Patch(p) => write!(f, "patch {} {} {} {}", p.address, p.expected, p.value, p.name),
+            LogEntry::Input(v) => write!(f, "in {}", v),
+            LogEntry::Output(v) => write!(f, "out {}", v),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ReplayError<W> {
+    Intcode(IntcodeError<W>),
+    Patch(PatchError<W>),
+    Io(String),
+    Parse { line: usize, content: String },
+    OutputMismatch { entry: usize, expected: W, found: W },
+    UnexpectedOutput { entry: usize, found: W },
+    UnexpectedInputRequest { entry: usize },
+    UnusedEntries { entry: usize },
+    MisplacedPatch { entry: usize },
+}
+
+impl<W: fmt::Display> fmt::Display for ReplayError<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Intcode(e) => write!(f, "{}", e),
+            ReplayError::Patch(e) => write!(f, "{}", e),
+            ReplayError::Io(e) => write!(f, "Log I/O failed: {}", e),
+            ReplayError::Parse { line, content } => write!(f, "Invalid log line {}: '{}'", line, content),
+            ReplayError::OutputMismatch { entry, expected, found } =>
+                write!(f, "Entry {} expected output {} but program produced {}", entry, expected, found),
+            ReplayError::UnexpectedOutput { entry, found } =>
+                write!(f, "Entry {} expected input but program produced {}", entry, found),
+            ReplayError::UnexpectedInputRequest { entry } =>
+                write!(f, "Entry {} expected output but program requested input", entry),
+            ReplayError::UnusedEntries { entry } => write!(f, "Program halted with entries left from {}", entry),
+            ReplayError::MisplacedPatch { entry } => write!(f, "Entry {} patches the program after it started", entry),
+        }
+    }
+}
+
+impl<W> From<IntcodeError<W>> for ReplayError<W> {
+    fn from(e: IntcodeError<W>) -> ReplayError<W> {
+        ReplayError::Intcode(e)
+    }
+}
+
+impl<W> From<PatchError<W>> for ReplayError<W> {
+    fn from(e: PatchError<W>) -> ReplayError<W> {
+        ReplayError::Patch(e)
+    }
+}
+
+impl<W> From<io::Error> for ReplayError<W> {
+    fn from(e: io::Error) -> ReplayError<W> {
+        ReplayError::Io(e.to_string())
+    }
+}
+
+/// Writes the log of one machine as it runs.
+#[derive(Debug)]
+pub(crate) struct Recorder {
+    writer: BufWriter<File>,
+}
+
+impl Recorder {
+    /// Starts the log at `path` with the patches the program was run with.
+    pub(crate) fn create<W: Word>(path: &Path, patches: &[Patch<W>]) -> io::Result<Recorder> {
+        let file = File::create(path).map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        let mut recorder = Recorder { writer: BufWriter::new(file) };
+        for patch in patches {
+            recorder.write(&LogEntry::Patch(patch.clone()))?;
+        }
+        Ok(recorder)
+    }
+
+    pub(crate) fn write<W: fmt::Display>(&mut self, entry: &LogEntry<W>) -> io::Result<()> {
+        writeln!(self.writer, "{}", entry)
+    }
+
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+pub fn read_log<W: Word>(path: &Path) -> Result<Vec<LogEntry<W>>, ReplayError<W>> {
+    let file = File::open(path)?;
+    let mut entries = Vec::new();
+    for (i, line) in io::BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        let parse_error = || ReplayError::Parse { line: i + 1, content: line.clone() };
+        let (kind, rest) = line.split_once(' ').ok_or_else(parse_error)?;
+        let entry = match kind {
+            "patch" => LogEntry::Patch(parse_patch(rest).ok_or_else(parse_error)?),
+            "in" => LogEntry::Input(rest.parse::<W>().map_err(|_| parse_error())?),
+            "out" => LogEntry::Output(rest.parse::<W>().map_err(|_| parse_error())?),
+            _ => return Err(parse_error()),
+        };
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+fn parse_patch<W: Word>(patch: &str) -> Option<Patch<W>> {
+    let mut parts = patch.splitn(4, ' ');
+    let address = parts.next()?.parse().ok()?;
+    let expected = parts.next()?.parse().ok()?;
+    let value = parts.next()?.parse().ok()?;
+    Some(Patch::new(parts.next()?, address, expected, value))
+}
+
+/// Runs like `try_run_program` while writing every consumed input and
+/// produced output to `log`.
+pub fn record_program<W: Word>(input: Receiver<W>, output: Sender<W>, program: &[W], log: &Path) -> Result<(W, HashMap<W, W>), ReplayError<W>> {
+    Ok(drive(IntcodeVm::recording(program, &[], log)?, input, output)?)
+}
+
+/// Runs `program` feeding it the inputs of `log`, and checks that it
+/// produces exactly the logged outputs in the logged order. The patches
+/// listed in the log are applied again, so `program` is the unpatched one.
+pub fn replay_program<W: Word>(program: &[W], log: &Path) -> Result<(W, HashMap<W, W>), ReplayError<W>> {
+    let entries = read_log(log)?;
+    replay_entries(program, &entries)
+}
+
+/// A log may end with the machine waiting for input, as it does when the
+/// recorded machine was dropped or explored before halting.
+pub fn replay_entries<W: Word>(program: &[W], entries: &[LogEntry<W>]) -> Result<(W, HashMap<W, W>), ReplayError<W>> {
+    let patches: Vec<Patch<W>> = entries.iter()
+        .map_while(|e| match e {
+            LogEntry::Patch(p) => Some(p.clone()),
+            _ => None,
+        })
+        .collect();
+    let mut patched = program.to_vec();
+    apply_patches(&mut patched, &patches)?;
+    let mut vm = IntcodeVm::load(&patched, None);
+    let mut io = W::zero();
+    let mut next = patches.len();
+    loop {
+        if let Some(LogEntry::Patch(_)) = entries.get(next) {
+            return Err(ReplayError::MisplacedPatch { entry: next });
+        }
+        let event = vm.run_until_event()?;
+        match (event, entries.get(next)) {
+            (Event::Output(found), Some(LogEntry::Output(expected))) => {
+                if found != *expected {
+                    return Err(ReplayError::OutputMismatch { entry: next, expected: expected.clone(), found });
+                }
+                io = found;
+            }
+            (Event::Output(found), _) => return Err(ReplayError::UnexpectedOutput { entry: next, found }),
+            (Event::NeedInput, Some(LogEntry::Input(value))) => vm.push_input(value.clone()),
+            (Event::NeedInput, None) => return Ok((io, vm.into_memory())),
+            (Event::NeedInput, _) => return Err(ReplayError::UnexpectedInputRequest { entry: next }),
+            (Event::Halted, None) => return Ok((io, vm.into_memory())),
+            (Event::Halted, Some(_)) => return Err(ReplayError::UnusedEntries { entry: next }),
+        }
+        next += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::sync::mpsc;
+
+    use crate::intcode_computer::read_program;
+
+    const ADD_TWO_INPUTS: &str = "3,11,3,12,1,11,12,13,4,13,99,0,0,0";
+
+    fn temp_log(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("intcode-replay-{}-{}.log", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let program: Vec<i128> = read_program(ADD_TWO_INPUTS);
+        let log = temp_log("record");
+        let (input_sender, input_receiver) = mpsc::channel();
+        let (output_sender, output_receiver) = mpsc::channel();
+        input_sender.send(3).ok();
+        input_sender.send(4).ok();
+        let (io, _) = record_program(input_receiver, output_sender, &program, &log).unwrap();
+        assert_eq!(io, 7);
+        assert_eq!(output_receiver.recv(), Ok(7));
+
+        let entries: Vec<LogEntry<i128>> = read_log(&log).unwrap();
+        assert_eq!(entries, vec![LogEntry::Input(3), LogEntry::Input(4), LogEntry::Output(7)]);
+        let (io, memory) = replay_program(&program, &log).unwrap();
+        assert_eq!(io, 7);
+        assert_eq!(memory[&13], 7);
+        std::fs::remove_file(&log).ok();
+    }
+
+    #[test]
+    fn test_machine_records_its_own_run() {
+        let program: Vec<i128> = read_program(ADD_TWO_INPUTS);
+        let log = temp_log("machine");
+        let mut vm = IntcodeVm::recording(&program, &[], &log).unwrap().with_inputs([3]);
+        assert_eq!(vm.next(), None);
+        // The fork runs on its own, only the original writes the log.
+        let mut fork = vm.clone().with_inputs([5]);
+        assert_eq!(fork.next(), Some(8));
+        vm.push_input(4);
+        assert_eq!(vm.collect::<Vec<i128>>(), vec![7]);
+        assert_eq!(read_log::<i128>(&log).unwrap(), vec![LogEntry::Input(3), LogEntry::Input(4), LogEntry::Output(7)]);
+        std::fs::remove_file(&log).ok();
+    }
+
+    #[test]
+    fn test_iterator_run_is_recorded() {
+        // Draws a tile, then reads the joystick and shows it as the score.
+        let program: Vec<i128> = read_program("104,1,104,2,104,3,3,100,104,-1,104,0,4,100,99");
+        let log = temp_log("chunks");
+        let mut screen = IntcodeVm::recording(&program, &[], &log).unwrap().chunks(3);
+        assert_eq!(screen.next(), Some(vec![1, 2, 3]));
+        assert_eq!(screen.next(), None);
+        screen.push_input(-1);
+        assert_eq!(screen.collect::<Vec<Vec<i128>>>(), vec![vec![-1, 0, -1]]);
+
+        let mut expected: Vec<LogEntry<i128>> = [1, 2, 3].iter().map(|o| LogEntry::Output(*o)).collect();
+        expected.push(LogEntry::Input(-1));
+        expected.extend([-1, 0, -1].iter().map(|o| LogEntry::Output(*o)));
+        assert_eq!(read_log(&log).unwrap(), expected);
+        assert_eq!(replay_program(&program, &log).unwrap().0, -1);
+        std::fs::remove_file(&log).ok();
+    }
+
+    #[test]
+    fn test_patches_are_logged_and_applied_again() {
+        // Outputs the sum of its two inputs, or their product once patched.
+        let program: Vec<i128> = read_program(ADD_TWO_INPUTS);
+        let patches = [Patch::new("multiply instead", 4, 1, 2)];
+        let log = temp_log("patches");
+        let outputs: Vec<i128> = IntcodeVm::recording(&program, &patches, &log).unwrap().with_inputs([3, 4]).collect();
+        assert_eq!(outputs, vec![12]);
+        assert_eq!(std::fs::read_to_string(&log).unwrap(), "patch 4 1 2 multiply instead\nin 3\nin 4\nout 12\n");
+        assert_eq!(replay_program(&program, &log).unwrap().0, 12);
+
+        let mut patched = program.clone();
+        patched[4] = 2;
+        let mismatch = PatchError::Mismatch { name: "multiply instead".to_string(), address: 4, expected: 1, found: 2 };
+        assert_eq!(replay_program(&patched, &log), Err(ReplayError::Patch(mismatch)));
+        let late_patch = [LogEntry::Input(3), LogEntry::Patch(patches[0].clone())];
+        assert_eq!(replay_entries(&program, &late_patch), Err(ReplayError::MisplacedPatch { entry: 1 }));
+        std::fs::remove_file(&log).ok();
+    }
+
+    #[test]
+    fn test_replay_detects_divergence() {
+        let program: Vec<i128> = read_program(ADD_TWO_INPUTS);
+        let wrong_output = [LogEntry::Input(3), LogEntry::Input(4), LogEntry::Output(8)];
+        assert_eq!(replay_entries(&program, &wrong_output), Err(ReplayError::OutputMismatch { entry: 2, expected: 8, found: 7 }));
+        let missing_input = [LogEntry::Input(3), LogEntry::Output(7)];
+        assert_eq!(replay_entries(&program, &missing_input), Err(ReplayError::UnexpectedInputRequest { entry: 1 }));
+        let extra = [LogEntry::Input(3), LogEntry::Input(4), LogEntry::Output(7), LogEntry::Input(1)];
+        assert_eq!(replay_entries(&program, &extra), Err(ReplayError::UnusedEntries { entry: 3 }));
+    }
+
+    #[test]
+    fn test_log_failures_are_errors() {
+        let missing = std::env::temp_dir().join("intcode-replay-missing").join("run.log");
+        assert!(matches!(IntcodeVm::<i128>::recording(&[99], &[], &missing), Err(ReplayError::Io(_))));
+        if cfg!(target_os = "linux") {
+            // Writes to /dev/full fail once the buffered log is flushed.
+            let mut vm = IntcodeVm::recording(&[104_i128, 7, 99], &[], Path::new("/dev/full")).unwrap();
+            assert_eq!(vm.run_until_event(), Ok(Event::Output(7)));
+            assert!(matches!(vm.run_until_event(), Err(IntcodeError::Log { ip: 2, .. })));
+        }
+    }
+
+    #[test]
+    fn test_read_log_rejects_garbage() {
+        let log = temp_log("garbage");
+        std::fs::write(&log, "in 1\nsideways 2\n").unwrap();
+        assert_eq!(read_log::<i128>(&log), Err(ReplayError::Parse { line: 2, content: "sideways 2".to_string() }));
+        std::fs::remove_file(&log).ok();
+    }
+}
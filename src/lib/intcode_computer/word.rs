@@ -0,0 +1,88 @@
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+use num_bigint::BigInt;
+
+/// A value the Intcode machine can hold in a memory cell.
+///
+/// Arithmetic is checked: `None` means the result does not fit the word,
+/// which the machine reports as an overflow at the faulting instruction.
+pub trait Word: Clone + Eq + Ord + Hash + Debug + Display + FromStr + Send + Sync + 'static {
+    fn from_i64(value: i64) -> Self;
+    fn to_i64(&self) -> Option<i64>;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+
+    fn zero() -> Self {
+        Self::from_i64(0)
+    }
+
+    fn one() -> Self {
+        Self::from_i64(1)
+    }
+}
+
+macro_rules! impl_primitive_word {
+    ($($t:ty),*) => {$(
+        impl Word for $t {
+            fn from_i64(value: i64) -> Self {
+                <$t>::from(value)
+            }
+
+            fn to_i64(&self) -> Option<i64> {
+                i64::try_from(*self).ok()
+            }
+
+            fn checked_add(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_add(*self, *other)
+            }
+
+            fn checked_mul(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_mul(*self, *other)
+            }
+        }
+    )*};
+}
+
+impl_primitive_word!(i64, i128);
+
+impl Word for BigInt {
+    fn from_i64(value: i64) -> Self {
+        BigInt::from(value)
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        i64::try_from(self).ok()
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_arithmetic() {
+        assert_eq!(Word::checked_add(&i64::MAX, &1), None);
+        assert_eq!(Word::checked_mul(&(i64::MAX / 2), &3), None);
+        assert_eq!(Word::checked_mul(&(i64::MAX as i128), &3), Some(i64::MAX as i128 * 3));
+        let big = BigInt::from(i128::MAX);
+        assert_eq!(Word::checked_mul(&big, &big), Some(&big * &big));
+    }
+
+    #[test]
+    fn test_to_i64() {
+        assert_eq!(Word::to_i64(&-5_i128), Some(-5));
+        assert_eq!(Word::to_i64(&i128::MAX), None);
+        assert_eq!(Word::to_i64(&BigInt::from(99)), Some(99));
+    }
+}
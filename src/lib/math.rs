@@ -0,0 +1,206 @@
+//! Number theory on the primitive integer types.
+//!
+//! Modular functions take a positive modulus and reduce their arguments
+//! into `0..m` first, so negative inputs are fine for signed types. They
+//! never overflow as long as the modulus itself fits the type.
+
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// A primitive integer type.
+pub trait Integer: Copy + Ord + Debug
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Rem<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+    const MAX: Self;
+
+    fn two() -> Self {
+        Self::ONE + Self::ONE
+    }
+}
+
+/// A signed primitive integer type.
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const MAX: Self = <$t>::MAX;
+        }
+    )*};
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl Signed for $t {}
+    )*};
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+fn abs<T: Integer>(a: T) -> T {
+    if a < T::ZERO { T::ZERO - a } else { a }
+}
+
+/// The greatest common divisor, never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    abs(a)
+}
+
+/// The least common multiple, never negative. 0 if either number is 0.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    abs(a / gcd(a, b) * b)
+}
+
+/// The gcd of all numbers, 0 for none.
+pub fn gcd_all<T: Integer, I: IntoIterator<Item = T>>(numbers: I) -> T {
+    numbers.into_iter().fold(T::ZERO, gcd)
+}
+
+/// The lcm of all numbers, 1 for none.
+pub fn lcm_all<T: Integer, I: IntoIterator<Item = T>>(numbers: I) -> T {
+    numbers.into_iter().fold(T::ONE, lcm)
+}
+
+/// `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`.
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::ONE, T::ZERO);
+    let (mut y0, mut y1) = (T::ZERO, T::ONE);
+    while r1 != T::ZERO {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < T::ZERO { (-r0, -x0, -y0) } else { (r0, x0, y0) }
+}
+
+/// `a` reduced into `0..m`.
+pub fn modulo<T: Integer>(a: T, m: T) -> T {
+    let r = a % m;
+    if r < T::ZERO { r + m } else { r }
+}
+
+fn add_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= m - b { a - (m - b) } else { a + b }
+}
+
+/// `a * b mod m` by doubling, so the product never has to fit.
+pub fn mul_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    let two = T::two();
+    let (mut a, mut b) = (modulo(a, m), modulo(b, m));
+    let mut result = T::ZERO;
+    while b > T::ZERO {
+        if b % two == T::ONE {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b = b / two;
+    }
+    result
+}
+
+/// `base ^ exponent mod m` for a non-negative exponent.
+pub fn pow_mod<T: Integer>(base: T, mut exponent: T, m: T) -> T {
+    let two = T::two();
+    let mut base = modulo(base, m);
+    let mut result = modulo(T::ONE, m);
+    while exponent > T::ZERO {
+        if exponent % two == T::ONE {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exponent = exponent / two;
+    }
+    result
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inverse<T: Signed>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(modulo(a, m), m);
+    if g == T::ONE { Some(modulo(x, m)) } else { None }
+}
+
+/// Solves `x ≡ residue (mod modulus)` for every pair, returning the
+/// smallest non-negative `x` and the lcm of the moduli. The moduli don't
+/// have to be coprime; `None` if the congruences contradict each other.
+pub fn chinese_remainder<T: Signed>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut x = T::ZERO;
+    let mut m = T::ONE;
+    for &(residue, modulus) in congruences {
+        let residue = modulo(residue, modulus);
+        let g = gcd(m, modulus);
+        let difference = modulo(residue - x, modulus);
+        if difference % g != T::ZERO {
+            return None;
+        }
+        let step = modulus / g;
+        let k = mul_mod(difference / g, mod_inverse(m / g, step)?, step);
+        x = x + m * k;
+        m = m * step;
+    }
+    Some((x, m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(lcm(4u128, 6), 12);
+        assert_eq!(lcm(-4i32, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(gcd_all(vec![24usize, 36, 60]), 12);
+        assert_eq!(lcm_all(vec![18u64, 28, 44]), 2772);
+        assert_eq!(lcm_all(Vec::<i32>::new()), 1);
+    }
+
+    #[test]
+    fn test_extended_gcd_and_inverse() {
+        for &(a, b) in [(240i64, 46), (46, 240), (-7, 3), (17, 0), (0, 5)].iter() {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6i128, 9), None);
+    }
+
+    #[test]
+    fn test_modular_arithmetic() {
+        assert_eq!(modulo(-7, 3), 2);
+        assert_eq!(mul_mod(u64::MAX - 1, u64::MAX - 2, u64::MAX), 2);
+        assert_eq!(mul_mod(i64::MAX - 1, -2, i64::MAX), 2);
+        assert_eq!(pow_mod(4u32, 13, 497), 445);
+        assert_eq!(pow_mod(2i128, 0, 1), 0);
+        let m = 119_315_717_514_047i64;
+        assert_eq!(pow_mod(3, m - 1, m), 1);
+        assert_eq!(mul_mod(mod_inverse(12345, m).unwrap(), 12345, m), 1);
+    }
+
+    #[test]
+    fn test_chinese_remainder() {
+        assert_eq!(chinese_remainder(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(chinese_remainder(&[(0i64, 17), (-2, 13), (-3, 19)]), Some((3417, 4199)));
+        assert_eq!(chinese_remainder(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(chinese_remainder(&[(1, 4), (2, 6)]), None);
+        assert_eq!(chinese_remainder::<i32>(&[]), Some((0, 1)));
+    }
+}
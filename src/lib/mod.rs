@@ -1,9 +1,21 @@
+pub mod bisect;
+pub mod cycle;
+pub mod direction;
+pub mod flood;
+pub mod geometry;
+pub mod grid;
+pub mod image;
 pub mod intcode_computer;
+pub mod math;
+pub mod ocr;
+pub mod search;
+pub mod vector;
 
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
 use std::cmp::Eq;
+use std::ops::{Add, Mul, Neg, Sub};
 
 pub trait Solver {
     fn solve(&self, lines: Vec<String>, part_two: bool) -> String;
@@ -15,17 +27,7 @@ pub fn read_lines<P>(filename: P) -> Vec<String>
     io::BufReader::new(file).lines().filter_map(io::Result::ok).collect()
 }
 
-#[cfg(test)]
-pub fn test_solver(solver: &impl Solver, part_two: bool, input: &[&str], expected_answer: &str) {
-    let lines = input.into_iter()
-        .map(|s| s.to_owned().to_string())
-        .collect();
-
-    let answer = solver.solve(lines, part_two);;
-    assert_eq!(answer, expected_answer);
-}
-
-#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug)]
 pub struct Position {
     pub x: i32,
     pub y: i32
@@ -44,6 +46,64 @@ impl Position {
     pub fn up(&self) -> Position {
         return Position{x: self.x, y: self.y + 1}
     }
+
+    /// The four orthogonal neighbours, clockwise from `up`.
+    pub fn neighbors4(&self) -> [Position; 4] {
+        [self.up(), self.right(), self.down(), self.left()]
+    }
+
+    /// All eight surrounding positions, clockwise from `up`.
+    pub fn neighbors8(&self) -> [Position; 8] {
+        [self.up(), self.up().right(), self.right(), self.down().right(),
+            self.down(), self.down().left(), self.left(), self.up().left()]
+    }
+
+    /// Rotates a quarter turn counter-clockwise around the origin.
+    pub fn rotate_left(&self) -> Position {
+        Position{x: -self.y, y: self.x}
+    }
+
+    /// Rotates a quarter turn clockwise around the origin.
+    pub fn rotate_right(&self) -> Position {
+        Position{x: self.y, y: -self.x}
+    }
+
+    /// The sign of each coordinate, a unit step towards this offset.
+    pub fn signum(&self) -> Position {
+        Position{x: self.x.signum(), y: self.y.signum()}
+    }
+}
+
+impl Add for Position {
+    type Output = Position;
+
+    fn add(self, other: Position) -> Position {
+        Position{x: self.x + other.x, y: self.y + other.y}
+    }
+}
+
+impl Sub for Position {
+    type Output = Position;
+
+    fn sub(self, other: Position) -> Position {
+        Position{x: self.x - other.x, y: self.y - other.y}
+    }
+}
+
+impl Mul<i32> for Position {
+    type Output = Position;
+
+    fn mul(self, factor: i32) -> Position {
+        Position{x: self.x * factor, y: self.y * factor}
+    }
+}
+
+impl Neg for Position {
+    type Output = Position;
+
+    fn neg(self) -> Position {
+        Position{x: -self.x, y: -self.y}
+    }
 }
 
 pub fn manhattan_distance(p1: &Position, p2: &Position) -> i32 {
@@ -64,5 +124,41 @@ mod tests {
         assert_eq!(manhattan_distance(&Position{x: 1, y: 2}, &Position{x: -1, y: 5}), 5);
     }
 
+    #[test]
+    pub fn test_position_arithmetic() {
+        let a = Position{x: 1, y: 2};
+        let b = Position{x: -3, y: 5};
+        assert_eq!(a + b, Position{x: -2, y: 7});
+        assert_eq!(a - b, Position{x: 4, y: -3});
+        assert_eq!(b * 3, Position{x: -9, y: 15});
+        assert_eq!(-a, Position{x: -1, y: -2});
+        assert_eq!((a - b).signum(), Position{x: 1, y: -1});
+        assert_eq!(Position{x: 0, y: -7}.signum(), Position{x: 0, y: -1});
+    }
+
+    #[test]
+    pub fn test_position_rotation() {
+        let p = Position{x: 2, y: 1};
+        assert_eq!(p.rotate_left(), Position{x: -1, y: 2});
+        assert_eq!(p.rotate_right(), Position{x: 1, y: -2});
+        assert_eq!(p.rotate_left().rotate_right(), p);
+        assert_eq!(p.rotate_left().rotate_left(), -p);
+        assert_eq!(Position{x: 0, y: 1}.rotate_right(), Position{x: 0, y: 0}.right());
+    }
+
+    #[test]
+    pub fn test_position_neighbors() {
+        let p = Position{x: 3, y: -1};
+        assert_eq!(p.neighbors4(), [p.up(), p.right(), p.down(), p.left()]);
+        let n8 = p.neighbors8();
+        assert!(p.neighbors4().iter().all(|n| n8.contains(n)));
+        assert!(n8.iter().all(|n| n != &p && (n.x - p.x).abs() <= 1 && (n.y - p.y).abs() <= 1));
+        let mut sorted = n8.to_vec();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), 8);
+        assert_eq!(sorted[0], Position{x: 2, y: -2});
+    }
+
 }
 
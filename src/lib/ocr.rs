@@ -0,0 +1,222 @@
+//! Reads the block letters that some puzzles draw as their answer. Two fonts
+//! are known: 6 rows high with letters 4 wide drawn every 5 columns (Days 8
+//! and 11), and 10 rows high with letters 6 wide drawn every 8 columns.
+//! Letters are separated by blank columns, except after a letter that
+//! fills its whole cell like the small `Y`.
+
+use std::fmt;
+
+use crate::grid::Grid;
+
+const SMALL: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE: [(char, &str); 15] = [
+    ('A', "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#"),
+    ('B', "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####."),
+    ('C', ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####."),
+    ('E', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('F', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('G', ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#"),
+    ('H', "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#"),
+    ('J', "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###.."),
+    ('K', "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#"),
+    ('L', "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('N', "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#"),
+    ('P', "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('R', "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#"),
+    ('X', "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#"),
+    ('Z', "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######"),
+];
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum OcrError {
+    /// The letters are neither 6 nor 10 rows high.
+    UnsupportedHeight(usize),
+    /// Letter number `index` matches no glyph of the font.
+    UnrecognizedGlyph { index: usize, glyph: String },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::UnsupportedHeight(height) => write!(f, "No font is {} rows high", height),
+            OcrError::UnrecognizedGlyph { index, glyph } => write!(f, "Unrecognized glyph {}:\n{}", index, glyph),
+        }
+    }
+}
+
+fn lookup(font: &[(char, &str)], glyph: &str) -> Option<char> {
+    font.iter().find(|(_, g)| *g == glyph).map(|(c, _)| *c)
+}
+
+/// Cells past the end of a short row are unlit.
+fn lit(row: &[bool], x: usize) -> bool {
+    row.get(x).copied().unwrap_or(false)
+}
+
+fn render(rows: &[Vec<bool>], columns: std::ops::Range<usize>) -> String {
+    rows.iter()
+        .map(|row| columns.clone().map(|x| if lit(row, x) { '#' } else { '.' }).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Recognizes the letters in `rows` of lit cells. Blank rows and columns
+/// around the text are ignored, and rows may have different lengths.
+pub fn recognize(rows: &[Vec<bool>]) -> Result<String, OcrError> {
+    let lit_rows: Vec<usize> = (0..rows.len()).filter(|&y| rows[y].iter().any(|c| *c)).collect();
+    let rows = match (lit_rows.first(), lit_rows.last()) {
+        (Some(&first), Some(&last)) => &rows[first..=last],
+        _ => return Ok(String::new()),
+    };
+    let (font, pitch): (&[(char, &str)], usize) = match rows.len() {
+        6 => (&SMALL, 5),
+        10 => (&LARGE, 8),
+        height => return Err(OcrError::UnsupportedHeight(height)),
+    };
+
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let blank = |x: usize| rows.iter().all(|row| !lit(row, x));
+    let mut text = String::new();
+    let mut x = 0;
+    while x < width {
+        if blank(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && !blank(x) {
+            x += 1;
+        }
+        if let Some(letter) = lookup(font, &render(rows, start..x)) {
+            text.push(letter);
+            continue;
+        }
+        // Touching letters, split them at the pitch.
+        for left in (start..x).step_by(pitch) {
+            let glyph = render(rows, left..(left + pitch).min(x));
+            let letter = lookup(font, &glyph).ok_or(OcrError::UnrecognizedGlyph { index: text.len(), glyph })?;
+            text.push(letter);
+        }
+    }
+    Ok(text)
+}
+
+/// Recognizes text drawn with `lit` characters, one line per row.
+pub fn recognize_str(image: &str, lit: char) -> Result<String, OcrError> {
+    let lines: Vec<&str> = image.lines().collect();
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let rows: Vec<Vec<bool>> = lines.iter()
+        .map(|l| {
+            let mut row: Vec<bool> = l.chars().map(|c| c == lit).collect();
+            row.resize(width, false);
+            row
+        })
+        .collect();
+    recognize(&rows)
+}
+
+/// Recognizes the cells of `grid` accepted by `lit`, with y growing
+/// downwards as in `Grid::display`.
+pub fn recognize_grid<T, F: Fn(&T) -> bool>(grid: &Grid<T>, lit: F) -> Result<String, OcrError> {
+    let bounds = match grid.bounds() {
+        Some(bounds) => bounds,
+        None => return Ok(String::new()),
+    };
+    let rows: Vec<Vec<bool>> = (bounds.min.y..=bounds.max.y)
+        .map(|y| grid.row(y).map(|c| c.is_some_and(&lit)).collect())
+        .collect();
+    recognize(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws `text` the way the puzzles do, one letter every `pitch` columns.
+    fn draw(font: &[(char, &str)], text: &str, pitch: usize) -> String {
+        let height = font[0].1.lines().count();
+        let rows: Vec<String> = (0..height)
+            .map(|y| text.chars()
+                .map(|c| {
+                    let row = font.iter().find(|(l, _)| *l == c).unwrap().1.lines().nth(y).unwrap();
+                    format!("{:.<width$}", row, width = pitch)
+                })
+                .collect())
+            .collect();
+        rows.join("\n")
+    }
+
+    #[test]
+    fn test_small_font() {
+        let image = "\
+.###..####..##...##..#..#.
+.#..#.#....#..#.#..#.#.#..
+.#..#.###..#....#..#.##...
+.###..#....#....####.#.#..
+.#....#....#..#.#..#.#.#..
+.#....#.....##..#..#.#..#.
+";
+        assert_eq!(recognize_str(image, '#'), Ok("PFCAK".to_string()));
+        let alphabet: String = SMALL.iter().map(|(c, _)| *c).collect();
+        assert_eq!(recognize_str(&draw(&SMALL, &alphabet, 5), '#'), Ok(alphabet));
+    }
+
+    #[test]
+    fn test_letters_filling_their_cell() {
+        // Y is 5 wide, so it touches the next letter at the real pitch.
+        let image = draw(&SMALL, "YAYYZ", 5);
+        assert!(image.starts_with("#...#.##..#...##...#####"));
+        assert_eq!(recognize_str(&image, '#'), Ok("YAYYZ".to_string()));
+        assert_eq!(recognize_str(&draw(&LARGE, "XANZ", 8), '#'), Ok("XANZ".to_string()));
+    }
+
+    #[test]
+    fn test_large_font_and_grid() {
+        let rows: Vec<String> = (0..10)
+            .map(|y| ['H', 'X'].iter()
+                .map(|c| LARGE.iter().find(|(l, _)| l == c).unwrap().1.lines().nth(y).unwrap())
+                .collect::<Vec<&str>>()
+                .join(".."))
+            .collect();
+        let grid = Grid::parse(&rows, |c| Some(c == '#'));
+        assert_eq!(recognize_grid(&grid, |lit| *lit), Ok("HX".to_string()));
+        assert_eq!(recognize_grid(&Grid::<bool>::sparse(), |lit| *lit), Ok(String::new()));
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        // Trailing unlit cells trimmed, so every row has its own length.
+        let image = draw(&SMALL, "JL", 5);
+        let rows: Vec<Vec<bool>> = image.lines().map(|l| l.trim_end_matches('.').chars().map(|c| c == '#').collect()).collect();
+        assert_eq!(rows.iter().map(|r| r.len()).collect::<Vec<usize>>(), vec![6, 6, 6, 6, 6, 9]);
+        assert_eq!(recognize(&rows), Ok("JL".to_string()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(recognize_str("#\n#\n#", '#'), Err(OcrError::UnsupportedHeight(3)));
+        let error = recognize_str("####.#\n#..#.#\n#..#.#\n####.#\n#..#.#\n#..#.#", '#').unwrap_err();
+        assert_eq!(error, OcrError::UnrecognizedGlyph { index: 0, glyph: "####\n#..#\n#..#\n####\n#..#\n#..#".to_string() });
+        assert_eq!(error.to_string(), "Unrecognized glyph 0:\n####\n#..#\n#..#\n####\n#..#\n#..#");
+    }
+}
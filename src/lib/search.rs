@@ -0,0 +1,211 @@
+//! Shortest path searches over a graph given by a neighbour function. Each
+//! search records the distance to every node it settled and the node it was
+//! reached from, so both paths and distance maps can be read off the result.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+pub struct Search<N, C> {
+    pub distances: HashMap<N, C>,
+    parents: HashMap<N, N>,
+    /// The first node accepted by the goal predicate, if any.
+    pub goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C> Search<N, C> {
+    pub fn distance(&self, node: &N) -> Option<&C> {
+        self.distances.get(node)
+    }
+
+    /// The nodes from the start up to and including `to`.
+    pub fn path(&self, to: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(to) {
+            return None;
+        }
+        let mut path = vec![to.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search counting edges. Stops once a node accepted by
+/// `is_goal` is reached, pass `|_| false` for the full distance map.
+pub fn bfs<N, F, G>(start: N, neighbours: F, is_goal: G) -> Search<N, usize>
+    where N: Clone + Eq + Hash, F: Fn(&N) -> Vec<N>, G: Fn(&N) -> bool {
+    let mut search = Search { distances: HashMap::new(), parents: HashMap::new(), goal: None };
+    search.distances.insert(start.clone(), 0);
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        let distance = search.distances[&node] + 1;
+        for next in neighbours(&node) {
+            if !search.distances.contains_key(&next) {
+                search.distances.insert(next.clone(), distance);
+                search.parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+/// Heap entry ordered by priority alone, lowest first.
+struct Entry<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// A* search with edge costs from `neighbours`. `heuristic` must never
+/// overestimate the remaining cost to a goal, and should be consistent for
+/// the distances of nodes other than the goal to be exact.
+pub fn astar<N, C, F, G, H>(start: N, neighbours: F, is_goal: G, heuristic: H) -> Search<N, C>
+    where N: Clone + Eq + Hash, C: Copy + Ord + Default + Add<Output = C>,
+          F: Fn(&N) -> Vec<(N, C)>, G: Fn(&N) -> bool, H: Fn(&N) -> C {
+    let mut search = Search { distances: HashMap::new(), parents: HashMap::new(), goal: None };
+    let mut best: HashMap<N, C> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    best.insert(start.clone(), C::default());
+    heap.push(Entry { priority: heuristic(&start), cost: C::default(), node: start });
+
+    while let Some(Entry { cost, node, .. }) = heap.pop() {
+        if search.distances.contains_key(&node) || best.get(&node).is_some_and(|b| *b < cost) {
+            continue;
+        }
+        search.distances.insert(node.clone(), cost);
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        for (next, edge) in neighbours(&node) {
+            let next_cost = cost + edge;
+            if search.distances.contains_key(&next) || best.get(&next).is_some_and(|b| *b <= next_cost) {
+                continue;
+            }
+            best.insert(next.clone(), next_cost);
+            search.parents.insert(next.clone(), node.clone());
+            heap.push(Entry { priority: next_cost + heuristic(&next), cost: next_cost, node: next });
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm, A* without a heuristic.
+pub fn dijkstra<N, C, F, G>(start: N, neighbours: F, is_goal: G) -> Search<N, C>
+    where N: Clone + Eq + Hash, C: Copy + Ord + Default + Add<Output = C>,
+          F: Fn(&N) -> Vec<(N, C)>, G: Fn(&N) -> bool {
+    astar(start, neighbours, is_goal, |_| C::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{manhattan_distance, Position};
+    use crate::direction::Direction;
+    use crate::grid::Grid;
+    use std::collections::HashMap;
+
+    const MAZE: [&str; 5] = [
+        "#########",
+        "#S..#...#",
+        "#.#.#.#.#",
+        "#.#...#G#",
+        "#########",
+    ];
+
+    fn maze() -> (Grid<char>, Position, Position) {
+        let grid = Grid::parse(&MAZE, Some);
+        let find = |c| grid.iter().find(|(_, v)| **v == c).map(|(p, _)| p).unwrap();
+        let (start, goal) = (find('S'), find('G'));
+        (grid, start, goal)
+    }
+
+    fn open(grid: &Grid<char>, p: &Position) -> Vec<Position> {
+        Direction::ALL.iter().map(|d| d.step(p)).filter(|n| grid.get(n).is_some_and(|c| *c != '#')).collect()
+    }
+
+    #[test]
+    fn test_grid_searches_agree() {
+        let (grid, start, goal) = maze();
+        let by_bfs = bfs(start, |p| open(&grid, p), |p| *p == goal);
+        assert_eq!(by_bfs.goal, Some(goal));
+        assert_eq!(by_bfs.distance(&goal), Some(&12));
+        let path = by_bfs.path(&goal).unwrap();
+        assert_eq!(path.len(), 13);
+        assert_eq!((path[0], path[12]), (start, goal));
+        assert!(path.windows(2).all(|w| manhattan_distance(&w[0], &w[1]) == 1));
+
+        let weighted = |p: &Position| open(&grid, p).into_iter().map(|n| (n, 1)).collect();
+        let by_dijkstra = dijkstra(start, weighted, |p| *p == goal);
+        assert_eq!(by_dijkstra.distance(&goal), Some(&12));
+        let by_astar = astar(start, weighted, |p| *p == goal, |p| manhattan_distance(p, &goal));
+        assert_eq!(by_astar.distance(&goal), Some(&12));
+        assert!(by_astar.distances.len() <= by_dijkstra.distances.len());
+
+        let everything = bfs(start, |p| open(&grid, p), |_| false);
+        assert_eq!(everything.goal, None);
+        assert_eq!(everything.distances.len(), grid.iter().filter(|(_, c)| **c != '#').count());
+        assert_eq!(everything.path(&Position { x: 0, y: 0 }), None);
+    }
+
+    #[test]
+    fn test_dijkstra_prefers_cheap_detours() {
+        // a -> b costs 10 directly, or 3 through c and d.
+        let edges: HashMap<char, Vec<(char, u32)>> = vec![
+            ('a', vec![('b', 10), ('c', 1)]),
+            ('c', vec![('d', 1)]),
+            ('d', vec![('b', 1)]),
+        ].into_iter().collect();
+        let neighbours = |n: &char| edges.get(n).cloned().unwrap_or_default();
+        let search = dijkstra('a', neighbours, |n| *n == 'b');
+        assert_eq!(search.distance(&'b'), Some(&3));
+        assert_eq!(search.path(&'b'), Some(vec!['a', 'c', 'd', 'b']));
+    }
+
+    #[test]
+    fn test_orbit_graph() {
+        let orbits = ["COM)B", "B)C", "C)D", "D)E", "E)F", "B)G", "G)H", "D)I", "E)J", "J)K", "K)L", "K)YOU", "I)SAN"];
+        let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (center, satellite) in orbits.iter().map(|o| o.split_at(o.find(')').unwrap())) {
+            let satellite = &satellite[1..];
+            graph.entry(center).or_default().push(satellite);
+            graph.entry(satellite).or_default().push(center);
+        }
+        let neighbours = |n: &&str| graph[n].clone();
+        let from_com = bfs("COM", neighbours, |_| false);
+        let total: usize = from_com.distances.values().sum();
+        assert_eq!(total - from_com.distances["YOU"] - from_com.distances["SAN"], 42);
+        let transfers = bfs("K", neighbours, |n| *n == "I");
+        assert_eq!(transfers.distance(&"I"), Some(&4));
+        assert_eq!(transfers.path(&"I"), Some(vec!["K", "J", "E", "D", "I"]));
+    }
+}
@@ -0,0 +1,185 @@
+//! Fixed size integer vectors for puzzles in any number of dimensions.
+//! Arithmetic is component-wise, with scalar multiplication on the right.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+use crate::Position;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug)]
+pub struct Vector<const N: usize> {
+    pub coords: [i32; N],
+}
+
+impl<const N: usize> Vector<N> {
+    pub fn new(coords: [i32; N]) -> Vector<N> {
+        Vector { coords }
+    }
+
+    pub fn zero() -> Vector<N> {
+        Vector { coords: [0; N] }
+    }
+
+    /// The vector pointing one step along `axis`.
+    pub fn unit(axis: usize) -> Vector<N> {
+        let mut v = Vector::zero();
+        v[axis] = 1;
+        v
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &i32> {
+        self.coords.iter()
+    }
+
+    /// Applies `f` to every component.
+    pub fn map<F: Fn(i32) -> i32>(&self, f: F) -> Vector<N> {
+        let mut coords = self.coords;
+        coords.iter_mut().for_each(|c| *c = f(*c));
+        Vector { coords }
+    }
+
+    pub fn abs(&self) -> Vector<N> {
+        self.map(i32::abs)
+    }
+
+    pub fn signum(&self) -> Vector<N> {
+        self.map(i32::signum)
+    }
+
+    /// The sum of the absolute components.
+    pub fn manhattan(&self) -> i32 {
+        self.iter().map(|c| c.abs()).sum()
+    }
+
+    pub fn euclidean(&self) -> f64 {
+        self.iter().map(|&c| f64::from(c) * f64::from(c)).sum::<f64>().sqrt()
+    }
+}
+
+impl<const N: usize> From<[i32; N]> for Vector<N> {
+    fn from(coords: [i32; N]) -> Vector<N> {
+        Vector { coords }
+    }
+}
+
+impl From<Position> for Vector<2> {
+    fn from(p: Position) -> Vector<2> {
+        Vector { coords: [p.x, p.y] }
+    }
+}
+
+impl From<Vector<2>> for Position {
+    fn from(v: Vector<2>) -> Position {
+        Position { x: v[0], y: v[1] }
+    }
+}
+
+impl<const N: usize> Index<usize> for Vector<N> {
+    type Output = i32;
+
+    fn index(&self, axis: usize) -> &i32 {
+        &self.coords[axis]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for Vector<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut i32 {
+        &mut self.coords[axis]
+    }
+}
+
+impl<const N: usize> Add for Vector<N> {
+    type Output = Vector<N>;
+
+    fn add(mut self, other: Vector<N>) -> Vector<N> {
+        self += other;
+        self
+    }
+}
+
+impl<const N: usize> AddAssign for Vector<N> {
+    fn add_assign(&mut self, other: Vector<N>) {
+        self.coords.iter_mut().zip(other.iter()).for_each(|(a, b)| *a += b);
+    }
+}
+
+impl<const N: usize> Sub for Vector<N> {
+    type Output = Vector<N>;
+
+    fn sub(mut self, other: Vector<N>) -> Vector<N> {
+        self -= other;
+        self
+    }
+}
+
+impl<const N: usize> SubAssign for Vector<N> {
+    fn sub_assign(&mut self, other: Vector<N>) {
+        self.coords.iter_mut().zip(other.iter()).for_each(|(a, b)| *a -= b);
+    }
+}
+
+impl<const N: usize> Mul<i32> for Vector<N> {
+    type Output = Vector<N>;
+
+    fn mul(self, factor: i32) -> Vector<N> {
+        self.map(|c| c * factor)
+    }
+}
+
+impl<const N: usize> Neg for Vector<N> {
+    type Output = Vector<N>;
+
+    fn neg(self) -> Vector<N> {
+        self.map(|c| -c)
+    }
+}
+
+/// Formats as `<1, -2, 3>`.
+impl<const N: usize> fmt::Display for Vector<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let coords: Vec<String> = self.iter().map(|c| c.to_string()).collect();
+        write!(f, "<{}>", coords.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Vector::new([1, -2, 3]);
+        let b = Vector::from([4, 5, -6]);
+        assert_eq!(a + b, Vector::new([5, 3, -3]));
+        assert_eq!(a - b, Vector::new([-3, -7, 9]));
+        assert_eq!(a * 2, Vector::new([2, -4, 6]));
+        assert_eq!(-a, Vector::new([-1, 2, -3]));
+        assert_eq!((b - a).signum(), Vector::new([1, 1, -1]));
+        let mut c = a;
+        c += Vector::unit(1);
+        c -= Vector::unit(0);
+        assert_eq!(c, Vector::new([0, -1, 3]));
+        assert_eq!(Vector::<4>::zero().iter().count(), 4);
+    }
+
+    #[test]
+    fn test_norms() {
+        let v = Vector::new([3, -4]);
+        assert_eq!(v.manhattan(), 7);
+        assert_eq!(v.abs(), Vector::new([3, 4]));
+        assert!((v.euclidean() - 5.0).abs() < 1e-9);
+        assert_eq!(Vector::new([1, 2, -3, 4]).manhattan(), 10);
+        assert_eq!(v.to_string(), "<3, -4>");
+    }
+
+    #[test]
+    fn test_axes() {
+        let mut v = Vector::new([7, 8, 9]);
+        assert_eq!((v[0], v[1], v[2]), (7, 8, 9));
+        v[2] = 0;
+        assert_eq!(v.iter().cloned().collect::<Vec<i32>>(), vec![7, 8, 0]);
+        let p = Position { x: 2, y: -5 };
+        assert_eq!(Position::from(Vector::from(p)), p);
+        assert_eq!(Vector::from(p) + Vector::new([1, 1]), Vector::from(p.right().up()));
+    }
+}
@@ -1,9 +1,13 @@
 //#![feature(crate_in_paths)]
 
+use std::env;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Instant;
 
-mod lib;
+use advent_of_code_2019 as lib;
 use lib::{Solver, read_lines};
+use lib::intcode_computer::{IntcodeVm, Patch};
 
 mod day01;
 use day01::Day1Solver;
@@ -67,6 +71,21 @@ fn get_solver(day: i32) -> (Box<dyn Solver>, String) {
 
 }
 
+static RECORDED_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+/// Loads an Intcode machine for a day. Setting `INTCODE_RECORD=<prefix>`
+/// records each one to its own `<prefix>.<n>.log` for replay.
+fn intcode_vm(program: &[i128], patches: &[Patch<i128>]) -> IntcodeVm {
+    let vm = match env::var("INTCODE_RECORD") {
+        Ok(prefix) => {
+            let log = format!("{}.{}.log", prefix, RECORDED_RUNS.fetch_add(1, Ordering::SeqCst));
+            IntcodeVm::recording(program, patches, Path::new(&log)).map_err(|e| e.to_string())
+        }
+        Err(_) => IntcodeVm::patched(program, patches).map_err(|e| e.to_string()),
+    };
+    vm.unwrap_or_else(|e| panic!("{}", e))
+}
+
 fn main() {
     let day = 17;
     let (solver, file) = get_solver(day);
@@ -75,3 +94,13 @@ fn main() {
     println!("Answer: {}", answer);
     println!("Time: {}", now.elapsed().as_secs_f32());
 }
+
+#[cfg(test)]
+fn test_solver(solver: &impl Solver, part_two: bool, input: &[&str], expected_answer: &str) {
+    let lines = input.into_iter()
+        .map(|s| s.to_owned().to_string())
+        .collect();
+
+    let answer = solver.solve(lines, part_two);
+    assert_eq!(answer, expected_answer);
+}